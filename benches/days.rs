@@ -0,0 +1,120 @@
+#[macro_use]
+extern crate criterion;
+extern crate advent_of_code_2018;
+
+use std::path::Path;
+
+use criterion::{Benchmark, Criterion};
+
+use advent_of_code_2018::input::Input;
+use advent_of_code_2018::solution::Solution;
+use advent_of_code_2018::*;
+
+/// Benchmarks parsing and both parts of a day, with part 2 run against its own input since some
+/// days have a separate example for it.
+fn bench_solution<S>(c: &mut Criterion, group: &str, part1_input: Input, part2_input: Input)
+where
+    S: Solution + 'static,
+{
+    let parse_input = part1_input.clone();
+    let benchmark = Benchmark::new("parse", move |b| {
+        b.iter(|| S::parse(&parse_input).unwrap())
+    })
+    .with_function("part 1", move |b| {
+        let parsed = S::parse(&part1_input).unwrap();
+        b.iter(|| S::part1(&parsed).unwrap())
+    })
+    .with_function("part 2", move |b| {
+        let parsed = S::parse(&part2_input).unwrap();
+        b.iter(|| S::part2(&parsed).unwrap())
+    });
+    c.bench(group, benchmark);
+}
+
+fn bench_test_input<S>(c: &mut Criterion, day: u8, part1_input: Input, part2_input: Input)
+where
+    S: Solution + 'static,
+{
+    let group = format!("test input/day {}", day);
+    bench_solution::<S>(c, &group, part1_input, part2_input);
+}
+
+/// Puzzle inputs aren't checked in for every day, so days without one are skipped.
+fn bench_puzzle_input<S>(c: &mut Criterion, day: u8, input: &str)
+where
+    S: Solution + 'static,
+{
+    if !Path::new(input).exists() {
+        return;
+    }
+    let group = format!("puzzle input/day {}", day);
+    bench_solution::<S>(c, &group, Input::file(input), Input::file(input));
+}
+
+fn test_file(name: &str) -> Input {
+    Input::file(format!("inputs/{}.txt", name))
+}
+
+fn bench_test_inputs(c: &mut Criterion) {
+    bench_test_input::<day1::Day1>(c, 1, test_file("1_test"), test_file("1_test_part2"));
+    bench_test_input::<day2::Day2>(c, 2, test_file("2_test"), test_file("2_test_part2"));
+    bench_test_input::<day3::Day3>(c, 3, test_file("3_test"), test_file("3_test"));
+    bench_test_input::<day4::Day4>(c, 4, test_file("4_test"), test_file("4_test"));
+    bench_test_input::<day5::Day5>(c, 5, test_file("5_test"), test_file("5_test"));
+    bench_test_input::<day6::Day6>(c, 6, test_file("6_test"), test_file("6_test"));
+    bench_test_input::<day7::Day7>(c, 7, test_file("7_test"), test_file("7_test"));
+    bench_test_input::<day8::Day8>(c, 8, test_file("8_test"), test_file("8_test"));
+    bench_test_input::<day9::Day9>(c, 9, test_file("9_test"), test_file("9_test"));
+    bench_test_input::<day10::Day10>(c, 10, test_file("10_test"), test_file("10_test"));
+    bench_test_input::<day11::Day11>(c, 11, Input::text("18"), Input::text("18"));
+    bench_test_input::<day12::Day12>(c, 12, test_file("12_test"), test_file("12_test"));
+    bench_test_input::<day13::Day13>(c, 13, test_file("13_test"), test_file("13_test_part2"));
+    bench_test_input::<day14::Day14>(c, 14, Input::text("51589"), Input::text("51589"));
+    bench_test_input::<day15::Day15>(c, 15, test_file("15_test"), test_file("15_test"));
+    bench_test_input::<day16::Day16>(c, 16, test_file("16_test"), test_file("16_test_part2"));
+    bench_test_input::<day17::Day17>(c, 17, test_file("17_test"), test_file("17_test"));
+    bench_test_input::<day18::Day18>(c, 18, test_file("18_test"), test_file("18_test"));
+    bench_test_input::<day19::Day19>(c, 19, test_file("19_test"), test_file("19_test_part2"));
+    bench_test_input::<day20::Day20>(c, 20, test_file("20_test"), test_file("20_test"));
+    bench_test_input::<day21::Day21>(c, 21, test_file("21_test"), test_file("21_test"));
+    bench_test_input::<day22::Day22>(c, 22, test_file("22_test"), test_file("22_test"));
+    bench_test_input::<day23::Day23>(c, 23, test_file("23_test"), test_file("23_test_part2"));
+    bench_test_input::<day24::Day24>(c, 24, test_file("24_test"), test_file("24_test"));
+    bench_test_input::<day25::Day25>(c, 25, test_file("25_test"), test_file("25_test"));
+}
+
+fn bench_puzzle_inputs(c: &mut Criterion) {
+    bench_puzzle_input::<day1::Day1>(c, 1, day1::INPUT);
+    bench_puzzle_input::<day2::Day2>(c, 2, day2::INPUT);
+    bench_puzzle_input::<day3::Day3>(c, 3, day3::INPUT);
+    bench_puzzle_input::<day4::Day4>(c, 4, day4::INPUT);
+    bench_puzzle_input::<day5::Day5>(c, 5, day5::INPUT);
+    bench_puzzle_input::<day6::Day6>(c, 6, day6::INPUT);
+    bench_puzzle_input::<day7::Day7>(c, 7, day7::INPUT);
+    bench_puzzle_input::<day8::Day8>(c, 8, day8::INPUT);
+    bench_puzzle_input::<day9::Day9>(c, 9, day9::INPUT);
+    bench_puzzle_input::<day10::Day10>(c, 10, day10::INPUT);
+    bench_puzzle_input::<day11::Day11>(c, 11, day11::INPUT);
+    bench_puzzle_input::<day12::Day12>(c, 12, day12::INPUT);
+    bench_puzzle_input::<day13::Day13>(c, 13, day13::INPUT);
+    bench_puzzle_input::<day14::Day14>(c, 14, day14::INPUT);
+    bench_puzzle_input::<day15::Day15>(c, 15, day15::INPUT);
+    bench_puzzle_input::<day16::Day16>(c, 16, day16::INPUT);
+    bench_puzzle_input::<day17::Day17>(c, 17, day17::INPUT);
+    bench_puzzle_input::<day18::Day18>(c, 18, day18::INPUT);
+    bench_puzzle_input::<day19::Day19>(c, 19, day19::INPUT);
+    bench_puzzle_input::<day20::Day20>(c, 20, day20::INPUT);
+    bench_puzzle_input::<day21::Day21>(c, 21, day21::INPUT);
+    bench_puzzle_input::<day22::Day22>(c, 22, day22::INPUT);
+    bench_puzzle_input::<day23::Day23>(c, 23, day23::INPUT);
+    bench_puzzle_input::<day24::Day24>(c, 24, day24::INPUT);
+    bench_puzzle_input::<day25::Day25>(c, 25, day25::INPUT);
+}
+
+criterion_group!(test_inputs, bench_test_inputs);
+criterion_group! {
+    name = puzzle_inputs;
+    config = Criterion::default().sample_size(10);
+    targets = bench_puzzle_inputs
+}
+criterion_main!(test_inputs, puzzle_inputs);
@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const ANSWERS: &str = "inputs/answers.txt";
+
+/// Generates a test for every day and part in the answers manifest, which `tests/answers.rs`
+/// includes.
+fn main() {
+    println!("cargo:rerun-if-changed={}", ANSWERS);
+    let answers = fs::read_to_string(ANSWERS).unwrap_or_default();
+    let mut generated = HashSet::new();
+    let mut tests = String::new();
+    for line in answers.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(' ').map(|field| field.parse::<u8>());
+        if let (Some(Ok(day)), Some(Ok(part))) = (fields.next(), fields.next()) {
+            if generated.insert((day, part)) {
+                tests.push_str(&format!(
+                    "#[test]\nfn day_{0}_part_{1}() {{\n    verify_answer({0}, {1});\n}}\n\n",
+                    day, part
+                ));
+            }
+        }
+    }
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("answers.rs"), tests).unwrap();
+}
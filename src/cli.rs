@@ -0,0 +1,382 @@
+use std::error::Error;
+use std::result;
+
+use advent_of_code_2018::input::Input;
+
+type Result<T> = result::Result<T, Box<dyn Error>>;
+
+pub const USAGE: &str = "\
+Usage:
+    advent-of-code-2018 run <day> [--part 1|2] [--input <path>|-] [--time] [--repeat <n>]
+                                   [--format text|json]
+    advent-of-code-2018 run --all [--part 1|2] [--time] [--repeat <n>] [--format text|json]
+                                  [--jobs <n>]
+    advent-of-code-2018 verify [<day>] [--answers <path>]
+    advent-of-code-2018 list
+    advent-of-code-2018 help
+
+Each day reads inputs/<day>.txt unless --input is given, where - reads from stdin, and
+--all skips days whose input file is missing.
+--time prints a table of parse and solve times, and --repeat runs each part n times
+and reports the min/median times. --format json prints a record per part with its answer,
+input hash, timings and any error. --jobs runs up to n days at once (one per core by default),
+still printing them in order.
+verify checks the solvers against the answers in inputs/answers.txt.";
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Day(u8),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        part: Option<u8>,
+        input: Option<Input>,
+        /// Number of times to run each part for a timing report, if one was asked for.
+        time: Option<usize>,
+        format: Format,
+        /// Number of days to run at once, or one per core if not given.
+        jobs: Option<usize>,
+    },
+    Verify {
+        day: Option<u8>,
+        answers: Option<Input>,
+    },
+    List,
+    Help,
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None | Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some("list") => match args.next() {
+            None => Ok(Command::List),
+            Some(arg) => Err(From::from(format!("Unexpected argument to list: {}", arg))),
+        },
+        Some("run") => parse_run_args(args),
+        Some("verify") => parse_verify_args(args),
+        Some(command) => Err(From::from(format!("Unknown command: {}", command))),
+    }
+}
+
+fn parse_run_args<I>(mut args: I) -> Result<Command>
+where
+    I: Iterator<Item = String>,
+{
+    let mut selection = None;
+    let mut part = None;
+    let mut input = None;
+    let mut time = None;
+    let mut format = Format::Text;
+    let mut jobs = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => set_selection(&mut selection, Selection::All)?,
+            "--time" | "-t" => time = time.or(Some(1)),
+            "--repeat" | "-r" => {
+                let value = args.next().ok_or("Missing value for --repeat")?;
+                time = Some(parse_repeat(&value)?);
+            }
+            "--format" | "-f" => {
+                let value = args.next().ok_or("Missing value for --format")?;
+                format = parse_format(&value)?;
+            }
+            "--jobs" | "-j" => {
+                let value = args.next().ok_or("Missing value for --jobs")?;
+                jobs = Some(parse_jobs(&value)?);
+            }
+            "--part" | "-p" => {
+                let value = args.next().ok_or("Missing value for --part")?;
+                part = Some(parse_part(&value)?);
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or("Missing value for --input")?;
+                input = Some(Input::from_arg(&value));
+            }
+            day => set_selection(&mut selection, Selection::Day(parse_day(day)?))?,
+        }
+    }
+    match selection {
+        Some(Selection::All) if input.is_some() => Err(From::from(
+            "--input can only be used when running a single day",
+        )),
+        Some(selection) => Ok(Command::Run {
+            selection,
+            part,
+            input,
+            time,
+            format,
+            jobs,
+        }),
+        None => Err(From::from("Missing day to run, pass a day number or --all")),
+    }
+}
+
+fn parse_verify_args<I>(mut args: I) -> Result<Command>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day = None;
+    let mut answers = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" | "-a" => {
+                let value = args.next().ok_or("Missing value for --answers")?;
+                answers = Some(Input::from_arg(&value));
+            }
+            value => {
+                if day.is_some() {
+                    return Err(From::from("Only one day can be verified at a time"));
+                }
+                day = Some(parse_day(value)?);
+            }
+        }
+    }
+    Ok(Command::Verify { day, answers })
+}
+
+fn set_selection(selection: &mut Option<Selection>, new_selection: Selection) -> Result<()> {
+    if selection.is_some() {
+        return Err(From::from("Only one day or --all can be run at a time"));
+    }
+    *selection = Some(new_selection);
+    Ok(())
+}
+
+fn parse_day(value: &str) -> Result<u8> {
+    match value.parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(From::from(format!("Invalid day: {}", value))),
+    }
+}
+
+fn parse_part(value: &str) -> Result<u8> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(From::from(format!("Invalid part: {}, must be 1 or 2", value))),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(From::from(format!("Invalid format: {}, must be text or json", value))),
+    }
+}
+
+fn parse_repeat(value: &str) -> Result<usize> {
+    match value.parse() {
+        Ok(repeat) if repeat > 0 => Ok(repeat),
+        _ => Err(From::from(format!("Invalid repeat count: {}", value))),
+    }
+}
+
+fn parse_jobs(value: &str) -> Result<usize> {
+    match value.parse() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(From::from(format!("Invalid job count: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_run_day() {
+        assert_eq!(
+            parse(&["run", "14"]).unwrap(),
+            Command::Run {
+                selection: Selection::Day(14),
+                part: None,
+                input: None,
+                time: None,
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_run_day_part() {
+        assert_eq!(
+            parse(&["run", "3", "--part", "2"]).unwrap(),
+            Command::Run {
+                selection: Selection::Day(3),
+                part: Some(2),
+                input: None,
+                time: None,
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_run_all() {
+        assert_eq!(
+            parse(&["run", "--all"]).unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: None,
+                time: None,
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_run_day_input() {
+        assert_eq!(
+            parse(&["run", "1", "--input", "inputs/1_test.txt"]).unwrap(),
+            Command::Run {
+                selection: Selection::Day(1),
+                part: None,
+                input: Some(Input::file("inputs/1_test.txt")),
+                time: None,
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+        assert_eq!(
+            parse(&["run", "1", "-i", "-"]).unwrap(),
+            Command::Run {
+                selection: Selection::Day(1),
+                part: None,
+                input: Some(Input::Stdin),
+                time: None,
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_run_timing() {
+        assert_eq!(
+            parse(&["run", "--all", "--time"]).unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: None,
+                time: Some(1),
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+        assert_eq!(
+            parse(&["run", "12", "--repeat", "5", "--time"]).unwrap(),
+            Command::Run {
+                selection: Selection::Day(12),
+                part: None,
+                input: None,
+                time: Some(5),
+                format: Format::Text,
+                jobs: None,
+            }
+        );
+        assert!(parse(&["run", "12", "--repeat", "0"]).is_err());
+        assert!(parse(&["run", "12", "--repeat"]).is_err());
+    }
+
+    #[test]
+    fn parses_run_format() {
+        assert_eq!(
+            parse(&["run", "10", "--format", "json"]).unwrap(),
+            Command::Run {
+                selection: Selection::Day(10),
+                part: None,
+                input: None,
+                time: None,
+                format: Format::Json,
+                jobs: None,
+            }
+        );
+        assert!(parse(&["run", "10", "--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn parses_run_jobs() {
+        assert_eq!(
+            parse(&["run", "--all", "--jobs", "4"]).unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: None,
+                time: None,
+                format: Format::Text,
+                jobs: Some(4),
+            }
+        );
+        assert!(parse(&["run", "--all", "-j", "0"]).is_err());
+        assert!(parse(&["run", "--all", "--jobs"]).is_err());
+    }
+
+    #[test]
+    fn parses_verify() {
+        assert_eq!(
+            parse(&["verify"]).unwrap(),
+            Command::Verify {
+                day: None,
+                answers: None,
+            }
+        );
+        assert_eq!(
+            parse(&["verify", "9", "--answers", "answers.txt"]).unwrap(),
+            Command::Verify {
+                day: Some(9),
+                answers: Some(Input::file("answers.txt")),
+            }
+        );
+        assert!(parse(&["verify", "9", "10"]).is_err());
+        assert!(parse(&["verify", "--answers"]).is_err());
+    }
+
+    #[test]
+    fn parses_list_and_help() {
+        assert_eq!(parse(&["list"]).unwrap(), Command::List);
+        assert_eq!(parse(&[]).unwrap(), Command::Help);
+        assert_eq!(parse(&["--help"]).unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn errors_on_invalid_run_args() {
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "0"]).is_err());
+        assert!(parse(&["run", "26"]).is_err());
+        assert!(parse(&["run", "1", "--part", "3"]).is_err());
+        assert!(parse(&["run", "1", "--part"]).is_err());
+        assert!(parse(&["run", "1", "--all"]).is_err());
+        assert!(parse(&["run", "1", "--input"]).is_err());
+        assert!(parse(&["run", "--all", "--input", "-"]).is_err());
+    }
+
+    #[test]
+    fn errors_on_unknown_command() {
+        assert_eq!(
+            parse(&["solve"]).unwrap_err().to_string(),
+            "Unknown command: solve"
+        );
+    }
+}
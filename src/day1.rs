@@ -1,60 +1,249 @@
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::collections::HashSet;
+use std::io::BufRead;
+use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = "inputs/1.txt";
-const LOOP_LIMIT: u16 = 1000;
+use crate::error::{Error, ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-pub fn solve_part1() -> Result<i32, Box<Error>> {
-    calculate_resulting_frequency(INPUT)
+pub const INPUT: &str = "inputs/1.txt";
+
+pub fn solve_part1(input: &Input) -> Result<i32> {
+    calculate_resulting_frequency(input)
+}
+
+pub fn solve_part2(input: &Input) -> Result<Option<i32>> {
+    find_repeating_frequency(input)
+}
+
+pub fn calculate_resulting_frequency(input: &Input) -> Result<i32> {
+    resulting_frequency(&read_frequencies(input)?)
+}
+
+/// Sums the changes, returning an error if the frequency no longer fits in an `i32`.
+pub fn resulting_frequency(freqs: &[i32]) -> Result<i32> {
+    freqs
+        .iter()
+        .enumerate()
+        .try_fold(0i32, |frequency, (index, adjustment)| {
+            frequency
+                .checked_add(*adjustment)
+                .ok_or_else(|| overflow_error(index + 1))
+        })
+}
+
+fn overflow_error(position: usize) -> Error {
+    Error::simulation(format!(
+        "Frequency overflows a 32-bit integer after change {}",
+        position
+    ))
+}
+
+pub fn find_repeating_frequency(input: &Input) -> Result<Option<i32>> {
+    let freqs = read_frequencies(input)?;
+    first_repeating_frequency(&freqs)
+}
+
+/// Where a frequency is first reached for the second time: on which pass through the list of
+/// changes (counting from 1) and after which change in it (also counting from 1).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Repeat {
+    pub frequency: i32,
+    pub pass: usize,
+    pub position: usize,
+}
+
+/// Everything about a list of frequency changes that can be worked out from a single pass through
+/// it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FrequencyReport {
+    pub changes: usize,
+    pub final_frequency: i32,
+    /// The lowest and highest frequencies reached during the first pass, counting the starting
+    /// frequency of 0.
+    pub min_frequency: i32,
+    pub max_frequency: i32,
+    pub first_repeat: Option<Repeat>,
 }
 
-pub fn solve_part2() -> Result<Option<i32>, Box<Error>> {
-    find_repeating_frequency(INPUT)
+/// Takes frequency changes one at a time, keeping only the frequency reached after each of them,
+/// and reports on them once they have all been seen.
+#[derive(Debug, Clone)]
+pub struct FrequencyAnalyzer {
+    sums: Vec<i32>,
+    seen: HashSet<i32>,
+    frequency: i32,
+    min_frequency: i32,
+    max_frequency: i32,
+    first_pass_repeat: Option<Repeat>,
 }
 
-fn calculate_resulting_frequency(filename: &str) -> Result<i32, Box<Error>> {
-    let mut freq: i32 = 0;
-    let file = File::open(filename)?;
-    for line in BufReader::new(file).lines() {
-        let adjustment: i32 = line?.parse()?;
-        freq += adjustment;
+impl FrequencyReport {
+    /// How far each pass shifts the frequency. Every pass starts where the last one ended, so
+    /// this is the frequency at the end of the first pass.
+    pub fn drift(&self) -> i32 {
+        self.final_frequency
     }
-    Ok(freq)
 }
 
-fn find_repeating_frequency(filename: &str) -> Result<Option<i32>, Box<Error>> {
-    let freqs = read_frequencies(filename)?;
-    let mut result_freqs = HashSet::new();
-    let mut freq: i32 = 0;
-    let mut loop_count = 0;
-    while loop_count < LOOP_LIMIT {
-        for adjustment in &freqs {
-            freq += adjustment;
-            if result_freqs.contains(&freq) {
-                return Ok(Some(freq))
-            } else {
-                result_freqs.insert(freq);
+impl Default for FrequencyAnalyzer {
+    fn default() -> FrequencyAnalyzer {
+        FrequencyAnalyzer::new()
+    }
+}
+
+impl FrequencyAnalyzer {
+    /// Starts at a frequency of 0, which counts as already seen.
+    pub fn new() -> FrequencyAnalyzer {
+        FrequencyAnalyzer {
+            sums: vec![],
+            seen: [0].iter().cloned().collect(),
+            frequency: 0,
+            min_frequency: 0,
+            max_frequency: 0,
+            first_pass_repeat: None,
+        }
+    }
+
+    /// Applies the next change, returning an error if the frequency no longer fits in an `i32`.
+    pub fn push(&mut self, adjustment: i32) -> Result<()> {
+        let position = self.sums.len() + 1;
+        self.frequency = self
+            .frequency
+            .checked_add(adjustment)
+            .ok_or_else(|| overflow_error(position))?;
+        if !self.seen.insert(self.frequency) && self.first_pass_repeat.is_none() {
+            self.first_pass_repeat = Some(Repeat {
+                frequency: self.frequency,
+                pass: 1,
+                position,
+            });
+        }
+        self.min_frequency = self.min_frequency.min(self.frequency);
+        self.max_frequency = self.max_frequency.max(self.frequency);
+        self.sums.push(self.frequency);
+        Ok(())
+    }
+
+    pub fn finish(self) -> FrequencyReport {
+        FrequencyReport {
+            changes: self.sums.len(),
+            final_frequency: self.frequency,
+            min_frequency: self.min_frequency,
+            max_frequency: self.max_frequency,
+            first_repeat: self
+                .first_pass_repeat
+                .or_else(|| repeat_after_first_pass(&self.sums)),
+        }
+    }
+}
+
+/// Finds the first frequency reached twice, given the frequencies reached during the first pass
+/// and that none of them repeat, counting the starting frequency of 0, without simulating pass
+/// after pass.
+///
+/// Every pass shifts the frequencies of the first pass by the same drift, so after `k` more passes
+/// the frequency `frequencies[j]` reached after `j` changes is shifted to `frequencies[j] + k *
+/// drift`, where `frequencies` starts with the 0 before any change and leaves out the frequency at
+/// the end of the pass, since that is where the next pass starts. That can only hit an earlier
+/// frequency `frequencies[i]` if the two are congruent modulo the drift, and for a given `j` the
+/// soonest hit is on the next frequency in its residue class in the direction of the drift. So
+/// sorting each residue class and comparing neighbors finds every candidate repeat, and the
+/// answer is the one that happens first. If no residue class has two frequencies in it, nothing
+/// ever repeats.
+fn repeat_after_first_pass(sums: &[i32]) -> Option<Repeat> {
+    let drift = i64::from(*sums.last()?);
+    let changes = sums.len() as i64;
+    let frequencies: Vec<i32> = std::iter::once(0)
+        .chain(sums[..sums.len() - 1].iter().cloned())
+        .collect();
+
+    let mut residue_classes: HashMap<i64, Vec<usize>> = HashMap::new();
+    for (index, frequency) in frequencies.iter().enumerate() {
+        residue_classes
+            .entry(i64::from(*frequency).rem_euclid(drift))
+            .or_default()
+            .push(index);
+    }
+    let mut first: Option<(i64, Repeat)> = None;
+    for indices in residue_classes.values_mut() {
+        indices.sort_unstable_by_key(|&index| frequencies[index]);
+        if drift < 0 {
+            indices.reverse();
+        }
+        for pair in indices.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let passes = (i64::from(frequencies[to]) - i64::from(frequencies[from])) / drift;
+            let steps = passes * changes + from as i64;
+            if first.is_none_or(|(first_steps, _)| steps < first_steps) {
+                let repeat = Repeat {
+                    frequency: frequencies[to],
+                    pass: ((steps - 1) / changes + 1) as usize,
+                    position: ((steps - 1) % changes + 1) as usize,
+                };
+                first = Some((steps, repeat));
             }
         }
-        loop_count += 1
     }
-    Ok(None)
+    first.map(|(_, repeat)| repeat)
+}
+
+pub fn analyze(freqs: &[i32]) -> Result<FrequencyReport> {
+    let mut analyzer = FrequencyAnalyzer::new();
+    for adjustment in freqs {
+        analyzer.push(*adjustment)?;
+    }
+    Ok(analyzer.finish())
+}
+
+/// Analyzes frequency changes as they are read, one per line, without holding on to the text.
+pub fn analyze_frequencies<R: BufRead>(reader: R) -> Result<FrequencyReport> {
+    let mut analyzer = FrequencyAnalyzer::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        analyzer.push(line.parse().on_line(index + 1, &line)?)?;
+    }
+    Ok(analyzer.finish())
+}
+
+pub fn first_repeating_frequency(freqs: &[i32]) -> Result<Option<i32>> {
+    Ok(first_repeat(freqs)?.map(|repeat| repeat.frequency))
 }
 
-fn read_frequencies(filename: &str) -> Result<Vec<i32>, Box<Error>> {
+pub fn first_repeat(freqs: &[i32]) -> Result<Option<Repeat>> {
+    Ok(analyze(freqs)?.first_repeat)
+}
+
+pub fn read_frequencies(input: &Input) -> Result<Vec<i32>> {
     let mut freqs: Vec<i32> = Vec::new();
-    let file = File::open(filename)?;
-    for line in BufReader::new(file).lines() {
-        freqs.push(line?.parse()?);
+    for (index, line) in input.reader()?.lines().enumerate() {
+        let line = line?;
+        freqs.push(line.parse().on_line(index + 1, &line)?);
     }
     Ok(freqs)
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<i32>;
+
+    fn parse(input: &Input) -> Result<Vec<i32>> {
+        read_frequencies(input)
+    }
+
+    fn part1(freqs: &Vec<i32>) -> Result<Answer> {
+        Ok(resulting_frequency(freqs)?.into())
+    }
+
+    fn part2(freqs: &Vec<i32>) -> Result<Answer> {
+        Ok(first_repeating_frequency(freqs)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::Lcg;
 
     const TEST_INPUT: &str = "inputs/1_test.txt";
     const TEST_INPUT_PART_2: &str = "inputs/1_test_part2.txt";
@@ -63,26 +252,151 @@ mod tests {
 
     #[test]
     fn finds_resulting_frequency() {
-        assert_eq!(calculate_resulting_frequency(TEST_INPUT).unwrap(), 3);
+        assert_eq!(calculate_resulting_frequency(&Input::file(TEST_INPUT)).unwrap(), 3);
     }
 
     #[test]
     fn finds_repeating_frequency() {
-        assert_eq!(find_repeating_frequency(TEST_INPUT_PART_2).unwrap().unwrap(), 14);
+        assert_eq!(find_repeating_frequency(&Input::file(TEST_INPUT_PART_2)).unwrap().unwrap(), 14);
     }
 
     #[test]
     fn finds_repeating_frequency_2() {
-        assert_eq!(find_repeating_frequency(TEST_INPUT_PART_2_2).unwrap().unwrap(), 10);
+        assert_eq!(find_repeating_frequency(&Input::file(TEST_INPUT_PART_2_2)).unwrap().unwrap(), 10);
     }
 
     #[test]
     fn finds_repeating_frequency_3() {
-        assert_eq!(find_repeating_frequency(TEST_INPUT_PART_2_3).unwrap().unwrap(), 5);
+        assert_eq!(find_repeating_frequency(&Input::file(TEST_INPUT_PART_2_3)).unwrap().unwrap(), 5);
+    }
+
+    #[test]
+    fn finds_repeats_beyond_many_passes() {
+        assert_eq!(first_repeating_frequency(&[10000, -9999]).unwrap(), Some(10000));
+        assert_eq!(
+            first_repeat(&[10000, -9999]).unwrap(),
+            Some(Repeat {
+                frequency: 10000,
+                pass: 10000,
+                position: 2,
+            })
+        );
+        assert_eq!(first_repeating_frequency(&[-10000, 9999]).unwrap(), Some(-10000));
+    }
+
+    #[test]
+    fn finds_repeat_of_starting_frequency() {
+        assert_eq!(first_repeating_frequency(&[1, -1]).unwrap(), Some(0));
+        assert_eq!(
+            first_repeat(&[1, -1]).unwrap(),
+            Some(Repeat {
+                frequency: 0,
+                pass: 1,
+                position: 2,
+            })
+        );
+        assert_eq!(
+            first_repeat(&[1, 2, -4]).unwrap(),
+            Some(Repeat {
+                frequency: 0,
+                pass: 2,
+                position: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn finds_no_repeat_when_there_is_none() {
+        assert_eq!(first_repeating_frequency(&[]).unwrap(), None);
+        assert_eq!(first_repeating_frequency(&[1, 1]).unwrap(), None);
+        assert_eq!(first_repeating_frequency(&[3, -1, 5]).unwrap(), None);
+    }
+
+    #[test]
+    fn matches_simulating_every_pass() {
+        // With up to 7 changes of at most 10, first pass frequencies are at most 140 apart, so
+        // any repeat happens within that many passes.
+        const SIMULATED_PASSES: usize = 141;
+        fn simulate(freqs: &[i32]) -> Option<Repeat> {
+            let mut seen = HashSet::new();
+            let mut freq = 0;
+            seen.insert(freq);
+            for pass in 1..=SIMULATED_PASSES {
+                for (index, adjustment) in freqs.iter().enumerate() {
+                    freq += adjustment;
+                    if !seen.insert(freq) {
+                        return Some(Repeat {
+                            frequency: freq,
+                            pass,
+                            position: index + 1,
+                        });
+                    }
+                }
+            }
+            None
+        }
+        let mut lcg = Lcg::new(1);
+        for _ in 0..500 {
+            let changes = lcg.below(7) + 1;
+            let freqs: Vec<i32> = (0..changes).map(|_| lcg.within(10)).collect();
+            assert_eq!(first_repeat(&freqs).unwrap(), simulate(&freqs), "{:?}", freqs);
+        }
+    }
+
+    #[test]
+    fn analyzes_frequencies() {
+        let report = analyze_frequencies(Input::file(TEST_INPUT_PART_2).reader().unwrap()).unwrap();
+        assert_eq!(
+            report,
+            FrequencyReport {
+                changes: 5,
+                final_frequency: 1,
+                min_frequency: 0,
+                max_frequency: 14,
+                first_repeat: Some(Repeat {
+                    frequency: 14,
+                    pass: 3,
+                    position: 3,
+                }),
+            }
+        );
+        assert_eq!(report.drift(), 1);
+        assert_eq!(analyze(&[-3, 1, -4]).unwrap().min_frequency, -6);
+    }
+
+    #[test]
+    fn errors_on_overflowing_frequency() {
+        match analyze_frequencies(Input::text("+2147483000\n+647\n+1\n").reader().unwrap()) {
+            Ok(_) => panic!("analyze_frequencies should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "Frequency overflows a 32-bit integer after change 3",
+            ),
+        }
+        assert!(first_repeat(&[i32::MIN, -1]).is_err());
+        assert!(Day1::part1(&vec![i32::MAX, 1]).is_err());
+        match resulting_frequency(&[i32::MAX, -1, 2]) {
+            Ok(_) => panic!("resulting_frequency should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "Frequency overflows a 32-bit integer after change 3",
+            ),
+        }
     }
 
     #[test]
     fn reads_frequencies_file() {
-        assert_eq!(read_frequencies(TEST_INPUT).unwrap(), vec![5, -5, 3]);
+        assert_eq!(read_frequencies(&Input::file(TEST_INPUT)).unwrap(), vec![5, -5, 3]);
+    }
+
+    #[test]
+    fn errors_on_malformed_frequency_line() {
+        match read_frequencies(&Input::text("+5\n-5\n+three\n")) {
+            Ok(_) => panic!("read_frequencies should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 3: invalid digit found in string: \"+three\"",
+            ),
+        }
     }
 }
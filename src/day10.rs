@@ -1,32 +1,31 @@
 extern crate regex;
 
 use std::collections::HashSet;
-use std::error::Error;
 use std::fmt;
-use std::fs;
-use std::result;
 use std::str::FromStr;
 
 use regex::Regex;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-const INPUT: &str = "inputs/10.txt";
+pub const INPUT: &str = "inputs/10.txt";
 
 #[derive(Debug, PartialEq, Clone)]
-struct Vector {
+pub struct Vector {
     x: i32,
     y: i32,
 }
 
-#[derive(Debug, PartialEq)]
-struct Point {
+#[derive(Debug, PartialEq, Clone)]
+pub struct Point {
     position: Vector,
     velocity: Vector,
 }
 
 impl FromStr for Point {
-    type Err = Box<Error>;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Point> {
         lazy_static! {
@@ -39,45 +38,46 @@ impl FromStr for Point {
 
         let captures = match RE.captures(s) {
             None => {
-                return Err(From::from("Malformed points, no fields could be found"));
+                return Err(Error::parse(1, s, "Malformed points, no fields could be found"));
             }
             Some(captures) => captures,
         };
 
         Ok(Point {
             position: Vector {
-                x: captures["position_x"].trim_start().parse()?,
-                y: captures["position_y"].trim_start().parse()?,
+                x: parse_field(&captures, "position_x", s)?,
+                y: parse_field(&captures, "position_y", s)?,
             },
             velocity: Vector {
-                x: captures["velocity_x"].trim_start().parse()?,
-                y: captures["velocity_y"].trim_start().parse()?,
+                x: parse_field(&captures, "velocity_x", s)?,
+                y: parse_field(&captures, "velocity_y", s)?,
             },
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Sky {
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sky {
     points: Vec<Point>,
 }
 
 impl FromStr for Sky {
-    type Err = Box<Error>;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Sky> {
         Ok(Sky {
             points: s
                 .trim_end()
                 .split('\n')
-                .map(|line| line.parse().unwrap())
-                .collect(),
+                .enumerate()
+                .map(|(index, line)| line.parse().map_err(|err: Error| err.at_line(index + 1)))
+                .collect::<Result<_>>()?,
         })
     }
 }
 
 impl Sky {
-    fn point_spread(&self) -> (Vector, Vector) {
+    pub fn point_spread(&self) -> (Vector, Vector) {
         let mut min = self.points[0].position.clone();
         let mut max = self.points[0].position.clone();
 
@@ -99,12 +99,41 @@ impl Sky {
         (min, max)
     }
 
-    fn move_points(&mut self, seconds: i32) {
+    pub fn move_points(&mut self, seconds: i32) {
         for point in self.points.iter_mut() {
             point.position.x += point.velocity.x * seconds;
             point.position.y += point.velocity.y * seconds;
         }
     }
+
+    pub fn move_until_message(&mut self) -> u32 {
+        let (min, max) = self.point_spread();
+        let mut min_spread = Vector {
+            x: (max.x - min.x).abs(),
+            y: (max.y - min.y).abs(),
+        };
+        let mut seconds = 0;
+
+        loop {
+            self.move_points(1);
+            let (min, max) = self.point_spread();
+            let spread_x = (max.x - min.x).abs();
+            let spread_y = (max.y - min.y).abs();
+
+            if spread_x > min_spread.x && spread_y > min_spread.y {
+                self.move_points(-1);
+                return seconds;
+            }
+
+            if spread_x < min_spread.x {
+                min_spread.x = spread_x
+            }
+            if spread_y < min_spread.y {
+                min_spread.y = spread_y
+            }
+            seconds += 1;
+        }
+    }
 }
 
 impl fmt::Display for Sky {
@@ -131,39 +160,38 @@ impl fmt::Display for Sky {
     }
 }
 
-pub fn solve_parts() -> Result<(String, u32)> {
-    let mut sky = read_points_file(INPUT)?;
-    let (min, max) = sky.point_spread();
-    let mut min_spread = Vector {
-        x: (max.x - min.x).abs(),
-        y: (max.y - min.y).abs(),
-    };
-    let mut seconds = 0;
+pub fn solve_parts(input: &Input) -> Result<(String, u32)> {
+    let mut sky = read_points_file(input)?;
+    let seconds = sky.move_until_message();
+    Ok((format!("{}", &sky), seconds))
+}
+
+pub fn read_points_file(input: &Input) -> Result<Sky> {
+    let points = input.read_to_string()?;
+    points.parse()
+}
 
-    loop {
-        sky.move_points(1);
-        let (min, max) = sky.point_spread();
-        let spread_x = (max.x - min.x).abs();
-        let spread_y = (max.y - min.y).abs();
+pub struct Day10;
 
-        if spread_x > min_spread.x && spread_y > min_spread.y {
-            sky.move_points(-1);
-            return Ok((format!("{}", &sky), seconds));
-        }
+impl Solution for Day10 {
+    type Parsed = Sky;
 
-        if spread_x < min_spread.x {
-            min_spread.x = spread_x
-        }
-        if spread_y < min_spread.y {
-            min_spread.y = spread_y
-        }
-        seconds += 1;
+    fn parse(input: &Input) -> Result<Sky> {
+        read_points_file(input)
+    }
+
+    fn part1(sky: &Sky) -> Result<Answer> {
+        let mut sky = sky.clone();
+        let seconds = sky.move_until_message();
+        Ok(Answer::Message {
+            lines: sky.to_string().lines().map(String::from).collect(),
+            seconds,
+        })
     }
-}
 
-fn read_points_file(filename: &str) -> Result<Sky> {
-    let points = fs::read_to_string(filename)?;
-    Ok(points.parse()?)
+    fn part2(sky: &Sky) -> Result<Answer> {
+        Ok(sky.clone().move_until_message().into())
+    }
 }
 
 #[cfg(test)]
@@ -195,13 +223,25 @@ mod tests {
     #[test]
     fn reads_points_file() {
         assert_eq!(
-            read_points_file(TEST_INPUT_ONE).unwrap(),
+            read_points_file(&Input::file(TEST_INPUT_ONE)).unwrap(),
             Sky {
                 points: vec![TEST_POINT_1]
             }
         );
     }
 
+    #[test]
+    fn errors_on_malformed_point_line() {
+        let points = "position=< 0,  1> velocity=< 0,  0>\nposition=< 0,  0> velocity=<0>\n";
+        match points.parse::<Sky>() {
+            Ok(_) => panic!("parsing the sky should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Malformed points, no fields could be found: \"position=< 0,  0> velocity=<0>\"",
+            ),
+        }
+    }
+
     #[test]
     fn displays_sky_with_one_point() {
         assert_eq!(format!("{}", test_sky()), "#\n#\n");
@@ -209,7 +249,7 @@ mod tests {
 
     #[test]
     fn displays_sky() {
-        let sky = read_points_file(TEST_INPUT).unwrap();
+        let sky = read_points_file(&Input::file(TEST_INPUT)).unwrap();
         print!("{}", &sky);
         assert_eq!(
             format!("{}", sky),
@@ -234,9 +274,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn moves_sky_until_message() {
+        let mut sky = read_points_file(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(sky.move_until_message(), 3);
+    }
+
     #[test]
     fn displays_message_in_sky() {
-        let mut sky = read_points_file(TEST_INPUT).unwrap();
+        let mut sky = read_points_file(&Input::file(TEST_INPUT)).unwrap();
         sky.move_points(3);
         print!("{}", &sky);
         assert_eq!(
@@ -1,11 +1,10 @@
-use std::error::Error;
 use std::fmt;
-use std::fs;
-use std::result;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-const INPUT: &str = "inputs/11.txt";
+pub const INPUT: &str = "inputs/11.txt";
 const GRID_SIZE: usize = 300;
 
 #[derive(Clone)]
@@ -32,7 +31,7 @@ impl fmt::Debug for Cells {
 }
 
 #[derive(Debug, Clone)]
-struct Grid {
+pub struct Grid {
     serial_number: usize,
     sums: Cells,
 }
@@ -62,14 +61,14 @@ impl Subsection {
 }
 
 impl Grid {
-    fn new(serial_number: usize) -> Grid {
+    pub fn new(serial_number: usize) -> Grid {
         Grid {
             serial_number,
             sums: Cells([[0; GRID_SIZE + 1]; GRID_SIZE + 1]),
         }
     }
 
-    fn power_at_cell(&self, coord: &Coordinate) -> i32 {
+    pub fn power_at_cell(&self, coord: &Coordinate) -> i32 {
         let rack_id = coord.x + 10;
         let mut power_level = rack_id * coord.y;
         power_level += self.serial_number;
@@ -78,7 +77,7 @@ impl Grid {
         power_level as i32 - 5
     }
 
-    fn fill_sums(&mut self) {
+    pub fn fill_sums(&mut self) {
         for x in 1..=GRID_SIZE {
             for y in 1..=GRID_SIZE {
                 let power = self.power_at_cell(&Coordinate { x, y });
@@ -88,14 +87,14 @@ impl Grid {
         }
     }
 
-    fn power_of_subsection(&mut self, subsection: &Subsection) -> i32 {
+    pub fn power_of_subsection(&mut self, subsection: &Subsection) -> i32 {
         let Subsection { coord, size } = subsection;
         let &Coordinate { x, y } = coord;
         self.sums.0[x][y] - self.sums.0[x - size][y] - self.sums.0[x][y - size]
             + self.sums.0[x - size][y - size]
     }
 
-    fn highest_power_subsection(&mut self, size: usize) -> (Subsection, i32) {
+    pub fn highest_power_subsection(&mut self, size: usize) -> (Subsection, i32) {
         let mut highest_power_subsection = Subsection {
             coord: Coordinate { x: size, y: size },
             size,
@@ -121,20 +120,29 @@ impl Grid {
     }
 }
 
-fn read_serial_number_file(filename: &str) -> Result<usize> {
-    let serial_number = fs::read_to_string(filename)?;
-    Ok(serial_number.trim().parse()?)
+pub fn read_serial_number_file(input: &Input) -> Result<usize> {
+    let serial_number = input.read_to_string()?;
+    let serial_number = serial_number.trim();
+    serial_number.parse().on_line(1, serial_number)
 }
 
-pub fn solve_part1() -> Result<Subsection> {
-    let serial_number = read_serial_number_file(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<Subsection> {
+    let serial_number = read_serial_number_file(input)?;
+    Ok(highest_power_3x3_subsection(serial_number))
+}
+
+pub fn solve_part2(input: &Input) -> Result<Subsection> {
+    let serial_number = read_serial_number_file(input)?;
+    Ok(highest_power_subsection_of_any_size(serial_number))
+}
+
+pub fn highest_power_3x3_subsection(serial_number: usize) -> Subsection {
     let mut grid = Grid::new(serial_number);
     grid.fill_sums();
-    Ok(grid.highest_power_subsection(3).0)
+    grid.highest_power_subsection(3).0
 }
 
-pub fn solve_part2() -> Result<Subsection> {
-    let serial_number = read_serial_number_file(INPUT)?;
+pub fn highest_power_subsection_of_any_size(serial_number: usize) -> Subsection {
     let mut grid = Grid::new(serial_number);
     grid.fill_sums();
     let (mut highest_power_subsection, mut highest_power_level) = grid.highest_power_subsection(1);
@@ -146,7 +154,39 @@ pub fn solve_part2() -> Result<Subsection> {
             highest_power_level = power;
         }
     }
-    Ok(highest_power_subsection)
+    highest_power_subsection
+}
+
+impl From<Subsection> for Answer {
+    fn from(subsection: Subsection) -> Answer {
+        Answer::Subsection {
+            x: subsection.coord.x,
+            y: subsection.coord.y,
+            size: subsection.size,
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = usize;
+
+    fn parse(input: &Input) -> Result<usize> {
+        read_serial_number_file(input)
+    }
+
+    fn part1(serial_number: &usize) -> Result<Answer> {
+        let Subsection { coord, .. } = highest_power_3x3_subsection(*serial_number);
+        Ok(Answer::Coordinate {
+            x: coord.x,
+            y: coord.y,
+        })
+    }
+
+    fn part2(serial_number: &usize) -> Result<Answer> {
+        Ok(highest_power_subsection_of_any_size(*serial_number).into())
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +222,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn answers_with_subsection_coordinates() {
+        let subsection = Subsection {
+            coord: Coordinate { x: 90, y: 269 },
+            size: 16,
+        };
+        assert_eq!(Answer::from(subsection).to_string(), "90,269,16");
+    }
+
     #[test]
     fn returns_highest_power_subsection() {
         let mut grid = Grid::new(18);
@@ -1,26 +1,25 @@
 extern crate regex;
 
 use std::collections::{HashMap, VecDeque};
-use std::error::Error;
 use std::fmt;
-use std::fs;
-use std::result;
 use std::str::FromStr;
 
 use regex::Regex;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-const INPUT: &str = "inputs/12.txt";
+pub const INPUT: &str = "inputs/12.txt";
 
-#[derive(Debug, PartialEq)]
-struct Pots(VecDeque<bool>);
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pots(VecDeque<bool>);
 
-#[derive(Debug, PartialEq)]
-struct SpreadRules(HashMap<[bool; 5], bool>);
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpreadRules(HashMap<[bool; 5], bool>);
 
-#[derive(Debug, PartialEq)]
-struct GrowthSimulation {
+#[derive(Debug, PartialEq, Clone)]
+pub struct GrowthSimulation {
     pots: Pots,
     spread_rules: SpreadRules,
     generations: usize,
@@ -76,24 +75,34 @@ impl fmt::Display for GrowthSimulation {
 }
 
 impl FromStr for GrowthSimulation {
-    type Err = Box<Error>;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<GrowthSimulation> {
         let sections: Vec<&str> = s.split("\n\n").collect();
+        if sections.len() < 2 {
+            return Err(Error::parse(
+                s.lines().count().max(1),
+                s.lines().last().unwrap_or(""),
+                "Malformed input, no spread rules after the initial state",
+            ));
+        }
         let (initial_state_str, spread_rules_str) = (sections[0], sections[1]);
+        let spread_rules_line = initial_state_str.lines().count() + 2;
         let mut pots = Pots(VecDeque::new());
         let mut spread_rules = SpreadRules(HashMap::new());
 
         lazy_static! {
-            static ref initial_state_regex: Regex =
+            static ref INITIAL_STATE_REGEX: Regex =
                 Regex::new(r"initial state: (?P<initial_state>[#.]+)").unwrap();
-            static ref spread_rules_regex: Regex =
+            static ref SPREAD_RULES_REGEX: Regex =
                 Regex::new(r"(?P<pattern>[#.]{5}) => (?P<result>[#.])").unwrap();
         }
 
-        let initial_state_captures = match initial_state_regex.captures(initial_state_str) {
+        let initial_state_captures = match INITIAL_STATE_REGEX.captures(initial_state_str) {
             None => {
-                return Err(From::from(
+                return Err(Error::parse(
+                    1,
+                    initial_state_str,
                     "Malformed initial state, no fields could be found",
                 ));
             }
@@ -104,10 +113,12 @@ impl FromStr for GrowthSimulation {
             pots.0.push_back(pot == '#');
         }
 
-        for rule in spread_rules_str.lines() {
-            let spread_rules_captures = match spread_rules_regex.captures(rule) {
+        for (index, rule) in spread_rules_str.lines().enumerate() {
+            let spread_rules_captures = match SPREAD_RULES_REGEX.captures(rule) {
                 None => {
-                    return Err(From::from(
+                    return Err(Error::parse(
+                        spread_rules_line + index,
+                        rule,
                         "Malformed spread rules, no fields could be found",
                     ));
                 }
@@ -129,12 +140,12 @@ impl FromStr for GrowthSimulation {
 }
 
 impl GrowthSimulation {
-    fn advance_generation(&mut self) {
+    pub fn advance_generation(&mut self) {
         let mut next_generation = VecDeque::new();
         let padding = &[false; 4];
         let pots_slice = self.pots.0.as_slices();
         let pots_slice = [padding, pots_slice.0, pots_slice.1, padding].concat();
-        for (index, pot_window) in pots_slice.windows(5).enumerate() {
+        for pot_window in pots_slice.windows(5) {
             match self.spread_rules.0.get(pot_window) {
                 Some(result) => {
                     next_generation.push_back(*result);
@@ -148,37 +159,76 @@ impl GrowthSimulation {
         self.generations += 1;
     }
 
-    fn sum_plant_indices(&self) -> i32 {
-        let mut sum: i32 = 0;
+    pub fn sum_plant_indices(&self) -> i64 {
+        let mut sum: i64 = 0;
         for (index, pot) in self.pots.0.iter().enumerate() {
             if *pot {
-                let shifted_index = index as i32 - self.generations as i32 * 2;
+                let shifted_index = index as i64 - self.generations as i64 * 2;
                 sum += shifted_index;
             }
         }
         sum
     }
-}
 
-fn read_initial_state_and_rules(filename: &str) -> Result<GrowthSimulation> {
-    let input = fs::read_to_string(filename)?;
-    Ok(input.parse()?)
+    pub fn sum_plant_indices_after(&mut self, generations: usize) -> i64 {
+        let mut sum = self.sum_plant_indices();
+        let mut pattern = self.pots.plants();
+        while self.generations < generations {
+            self.advance_generation();
+            let next_sum = self.sum_plant_indices();
+            let next_pattern = self.pots.plants();
+            if next_pattern == pattern {
+                // The plants have settled into a pattern that only shifts along the row, so
+                // every remaining generation adds the same amount to the sum.
+                let remaining = (generations - self.generations) as i64;
+                return next_sum + remaining * (next_sum - sum);
+            }
+            sum = next_sum;
+            pattern = next_pattern;
+        }
+        sum
+    }
 }
 
-pub fn solve_part1() -> Result<i32> {
-    let mut growth_sim = read_initial_state_and_rules(INPUT)?;
-    for _ in 0..20 {
-        growth_sim.advance_generation();
+impl Pots {
+    fn plants(&self) -> Vec<bool> {
+        let first = self.0.iter().position(|pot| *pot).unwrap_or(0);
+        let last = self.0.iter().rposition(|pot| *pot).map_or(0, |last| last + 1);
+        self.0.iter().skip(first).take(last.saturating_sub(first)).cloned().collect()
     }
-    Ok(growth_sim.sum_plant_indices())
 }
 
-pub fn solve_part2() -> Result<i32> {
-    let mut growth_sim = read_initial_state_and_rules(INPUT)?;
-    for _ in 0..50_000_000_000_i64 {
-        growth_sim.advance_generation();
+pub fn read_initial_state_and_rules(input: &Input) -> Result<GrowthSimulation> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<i64> {
+    let mut growth_sim = read_initial_state_and_rules(input)?;
+    Ok(growth_sim.sum_plant_indices_after(20))
+}
+
+pub fn solve_part2(input: &Input) -> Result<i64> {
+    let mut growth_sim = read_initial_state_and_rules(input)?;
+    Ok(growth_sim.sum_plant_indices_after(50_000_000_000))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = GrowthSimulation;
+
+    fn parse(input: &Input) -> Result<GrowthSimulation> {
+        read_initial_state_and_rules(input)
+    }
+
+    fn part1(growth_sim: &GrowthSimulation) -> Result<Answer> {
+        Ok(growth_sim.clone().sum_plant_indices_after(20).into())
+    }
+
+    fn part2(growth_sim: &GrowthSimulation) -> Result<Answer> {
+        Ok(growth_sim.clone().sum_plant_indices_after(50_000_000_000).into())
     }
-    Ok(growth_sim.sum_plant_indices())
 }
 
 #[cfg(test)]
@@ -218,33 +268,59 @@ mod tests {
 
     #[test]
     fn reads_initial_state_and_rules_file() {
-        let growth_sim = read_initial_state_and_rules(TEST_INPUT).unwrap();
+        let growth_sim = read_initial_state_and_rules(&Input::file(TEST_INPUT)).unwrap();
         assert_eq!(growth_sim, test_growth_sim());
     }
 
+    #[test]
+    fn errors_on_malformed_spread_rule() {
+        let input = "initial state: #..#\n\n...## => #\n..#.. #\n";
+        match input.parse::<GrowthSimulation>() {
+            Ok(_) => panic!("parsing the growth simulation should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 4: Malformed spread rules, no fields could be found: \"..#.. #\"",
+            ),
+        }
+    }
+
+    #[test]
+    fn errors_on_empty_input_at_line_1() {
+        match "".parse::<GrowthSimulation>() {
+            Ok(_) => panic!("parsing the growth simulation should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 1: Malformed input, no spread rules after the initial state: \"\"",
+            ),
+        }
+    }
+
     #[test]
     fn displays_growth_simulation() {
-        assert_eq!(
-            format!("{}", test_growth_sim()).lines().collect::<Vec<&str>>().sort(),
-            vec![
-                "#..#.#..##......###...###",
-                "",
-                "####. => #",
-                "##.## => #",
-                "..#.. => #",
-                ".#... => #",
-                "##.#. => #",
-                ".#### => #",
-                "#.### => #",
-                "###.# => #",
-                ".#.## => #",
-                "#.#.# => #",
-                ".#.#. => #",
-                ".##.. => #",
-                "###.. => #",
-                "...## => #",
-            ].sort()
-        );
+        let display = format!("{}", test_growth_sim());
+        let mut lines: Vec<&str> = display.lines().collect();
+        lines.sort();
+        let mut expected = vec![
+            "#..#.#..##......###...###",
+            "",
+            "####. => #",
+            "##.## => #",
+            "..#.. => #",
+            ".#... => #",
+            "##.#. => #",
+            ".#### => #",
+            "#.### => #",
+            "###.# => #",
+            ".#.## => #",
+            "#.#.# => #",
+            ".#.#. => #",
+            ".##.. => #",
+            "###.. => #",
+            "...## => #",
+            "",
+        ];
+        expected.sort();
+        assert_eq!(lines, expected);
     }
 
     #[test]
@@ -262,4 +338,13 @@ mod tests {
         }
         assert_eq!(growth_sim.sum_plant_indices(), 325);
     }
+
+    #[test]
+    fn extrapolates_sum_once_pattern_settles() {
+        let mut growth_sim = test_growth_sim();
+        for _ in 0..500 {
+            growth_sim.advance_generation();
+        }
+        assert_eq!(test_growth_sim().sum_plant_indices_after(500), growth_sim.sum_plant_indices());
+    }
 }
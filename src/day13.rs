@@ -1,12 +1,11 @@
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
-use std::fs;
-use std::result;
 use std::str::FromStr;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-const INPUT: &str = "inputs/13.txt";
+pub const INPUT: &str = "inputs/13.txt";
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Vector {
@@ -15,7 +14,7 @@ pub struct Vector {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum Turn {
+pub enum Turn {
     Left,
     Straight,
     Right,
@@ -25,35 +24,29 @@ enum Turn {
 const INTER_SEQ: [Turn; 3] = [Turn::Left, Turn::Straight, Turn::Right];
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     North,
     South,
     East,
     West,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Cart {
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Cart {
     position: Vector,
     direction: Direction,
     next_turn: u8,
 }
 
-#[derive(Debug, PartialEq)]
-struct Track {
+#[derive(Debug, PartialEq, Clone)]
+pub struct Track {
     carts: Vec<Cart>,
     turns: HashMap<Vector, (Direction, Direction)>,
     intersections: HashSet<Vector>,
 }
 
-#[derive(Debug, PartialEq)]
-struct Collision {
-    position: Vector,
-    cart_indices: (usize, usize),
-}
-
 impl FromStr for Track {
-    type Err = Box<Error>;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Track> {
         let mut carts = vec![];
@@ -102,9 +95,12 @@ impl FromStr for Track {
                                 bottom_start = None;
                             }
                             None => {
-                                return Err(From::from(
+                                return Err(Error::parse(
+                                    row_index + 1,
+                                    row,
                                     "Malformed track, circuit bottom without top",
-                                ))
+                                )
+                                .at_column(col_index + 1))
                             }
                         },
                         None => {
@@ -214,7 +210,7 @@ impl Cart {
 }
 
 impl Track {
-    fn run_tick(&mut self, find_final_cart: bool) -> Option<Vector> {
+    pub fn run_tick(&mut self, find_final_cart: bool) -> Option<Vector> {
         let mut collided_cart_indices = HashSet::new();
         let mut cart_positions: HashMap<Vector, usize> = HashMap::new();
         for (index, cart) in self.carts.iter().enumerate() {
@@ -269,7 +265,7 @@ impl Track {
         None
     }
 
-    fn find_first_collision(&mut self) -> Vector {
+    pub fn find_first_collision(&mut self) -> Vector {
         let mut collision: Option<Vector> = None;
         while collision.is_none() {
             collision = self.run_tick(false);
@@ -277,7 +273,7 @@ impl Track {
         collision.unwrap()
     }
 
-    fn find_last_cart(&mut self) -> &Cart {
+    pub fn find_last_cart(&mut self) -> &Cart {
         while self.carts.len() != 1 {
             self.run_tick(true);
         }
@@ -285,21 +281,56 @@ impl Track {
     }
 }
 
-fn read_track(filename: &str) -> Result<Track> {
-    let input = fs::read_to_string(filename)?;
-    Ok(input.parse()?)
+pub fn read_track(input: &Input) -> Result<Track> {
+    let input = input.read_to_string()?;
+    input.parse()
 }
 
-pub fn solve_part1() -> Result<Vector> {
-    let mut track = read_track(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<Vector> {
+    let mut track = read_track(input)?;
     Ok(track.find_first_collision())
 }
 
-pub fn solve_part2() -> Result<Vector> {
-    let mut track = read_track(INPUT)?;
+pub fn solve_part2(input: &Input) -> Result<Vector> {
+    let mut track = read_track(input)?;
     Ok(track.find_last_cart().position)
 }
 
+impl From<Vector> for Answer {
+    fn from(position: Vector) -> Answer {
+        Answer::Coordinate {
+            x: position.x,
+            y: position.y,
+        }
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Track;
+
+    fn parse(input: &Input) -> Result<Track> {
+        read_track(input)
+    }
+
+    fn part1(track: &Track) -> Result<Answer> {
+        if track.carts.len() < 2 {
+            return Err(Error::simulation("Fewer than two carts on the track, none can collide"));
+        }
+        Ok(track.clone().find_first_collision().into())
+    }
+
+    fn part2(track: &Track) -> Result<Answer> {
+        if track.carts.len().is_multiple_of(2) {
+            return Err(Error::simulation(
+                "Even number of carts on the track, there will be no last cart",
+            ));
+        }
+        Ok(track.clone().find_last_cart().position.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,7 +350,7 @@ mod tests {
                     next_turn: 0,
                 },
             ],
-            turns: vec![
+            turns: [
                 (Vector { x: 0, y: 0 }, (Direction::East, Direction::South)),
                 (Vector { x: 4, y: 0 }, (Direction::South, Direction::West)),
                 (Vector { x: 4, y: 4 }, (Direction::West, Direction::North)),
@@ -336,7 +367,7 @@ mod tests {
             .iter()
             .cloned()
             .collect(),
-            intersections: vec![
+            intersections: [
                 Vector { x: 4, y: 2 },
                 Vector { x: 7, y: 2 },
                 Vector { x: 2, y: 4 },
@@ -350,7 +381,7 @@ mod tests {
 
     #[test]
     fn reads_track_file() {
-        let track = read_track(TEST_INPUT).unwrap();
+        let track = read_track(&Input::file(TEST_INPUT)).unwrap();
         assert_eq!(track, test_track());
     }
 
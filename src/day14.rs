@@ -1,14 +1,13 @@
-use std::error::Error;
 use std::fmt;
-use std::fs;
-use std::result;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{Error, ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-const INPUT: &str = "inputs/14.txt";
+pub const INPUT: &str = "inputs/14.txt";
 
 #[derive(Debug, PartialEq)]
-struct Recipes {
+pub struct Recipes {
     scores: Vec<u8>,
     elf1_index: usize,
     elf2_index: usize,
@@ -32,7 +31,7 @@ impl fmt::Display for Recipes {
 }
 
 impl Recipes {
-    fn new() -> Recipes {
+    pub fn new() -> Recipes {
         Recipes {
             scores: vec![3, 7],
             elf1_index: 0,
@@ -40,7 +39,7 @@ impl Recipes {
         }
     }
 
-    fn new_recipes(&mut self) {
+    pub fn new_recipes(&mut self) {
         let elf1_score = self.scores[self.elf1_index];
         let elf2_score = self.scores[self.elf2_index];
         let sum = elf1_score + elf2_score;
@@ -53,14 +52,14 @@ impl Recipes {
         push_digits(sum, &mut self.scores);
     }
 
-    fn pick_recipes(&mut self) {
+    pub fn pick_recipes(&mut self) {
         let elf1_score = self.scores[self.elf1_index];
         let elf2_score = self.scores[self.elf2_index];
         self.elf1_index = (self.elf1_index + (1 + elf1_score as usize)) % self.scores.len();
         self.elf2_index = (self.elf2_index + (1 + elf2_score as usize)) % self.scores.len();
     }
 
-    fn scores_after_n_recipes(&mut self, n: usize, num_scores: usize) -> String {
+    pub fn scores_after_n_recipes(&mut self, n: usize, num_scores: usize) -> String {
         while self.scores.len() < n + num_scores {
             self.new_recipes();
             self.pick_recipes();
@@ -68,7 +67,7 @@ impl Recipes {
         self.scores[n..n + num_scores].iter().map(|score| format!("{}", score)).collect()
     }
 
-    fn find_index_of_sequence(&mut self, seq: &[u8]) -> usize {
+    pub fn find_index_of_sequence(&mut self, seq: &[u8]) -> usize {
         let seq_len = seq.len();
         loop {
             let scores_len = self.scores.len();
@@ -88,32 +87,66 @@ impl Recipes {
     }
 }
 
-fn digit_seq(n: &str) -> Vec<u8> {
+impl Default for Recipes {
+    fn default() -> Recipes {
+        Recipes::new()
+    }
+}
+
+pub fn digit_seq(n: &str) -> Result<Vec<u8>> {
     let mut digits: Vec<u8> = vec![];
-    for digit in n.chars() {
-        digits.push(digit.to_digit(10).unwrap() as u8);
+    for (index, digit) in n.chars().enumerate() {
+        match digit.to_digit(10) {
+            Some(digit) => digits.push(digit as u8),
+            None => {
+                return Err(Error::parse(1, n, format!("Not a digit: {:?}", digit))
+                    .at_column(index + 1))
+            }
+        }
     }
-    digits
+    Ok(digits)
 }
 
-fn read_input_file(filename: &str) -> Result<usize> {
-    let input = fs::read_to_string(filename)?;
-    Ok(input.trim().parse()?)
+pub fn read_input_file(input: &Input) -> Result<usize> {
+    let input = input.read_to_string()?;
+    let input = input.trim();
+    input.parse().on_line(1, input)
 }
 
-pub fn solve_part1() -> Result<String> {
-    let input = read_input_file(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<String> {
+    let input = read_input_file(input)?;
     let mut recipes = Recipes::new();
     Ok(recipes.scores_after_n_recipes(input, 10))
 }
 
-pub fn solve_part2() -> Result<usize> {
-    let input = fs::read_to_string(INPUT)?;
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    let input = input.read_to_string()?;
     let mut recipes = Recipes::new();
-    let seq = digit_seq(&input.trim());
+    let seq = digit_seq(input.trim())?;
     Ok(recipes.find_index_of_sequence(&seq[..]))
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = String;
+
+    fn parse(input: &Input) -> Result<String> {
+        Ok(input.read_to_string()?.trim().to_string())
+    }
+
+    fn part1(input: &String) -> Result<Answer> {
+        let mut recipes = Recipes::new();
+        let n = input.parse().on_line(1, input)?;
+        Ok(recipes.scores_after_n_recipes(n, 10).into())
+    }
+
+    fn part2(input: &String) -> Result<Answer> {
+        let mut recipes = Recipes::new();
+        Ok(recipes.find_index_of_sequence(&digit_seq(input)?[..]).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,7 +227,7 @@ mod tests {
     #[test]
     fn finds_index_of_sequence_1() {
         let mut recipes = Recipes::new();
-        let seq = vec![5, 1, 5, 8, 9];
+        let seq = [5, 1, 5, 8, 9];
         assert_eq!(
             recipes.find_index_of_sequence(&seq[..]),
             9,
@@ -204,7 +237,7 @@ mod tests {
     #[test]
     fn finds_index_of_sequence_2() {
         let mut recipes = Recipes::new();
-        let seq = vec![0, 1, 2, 4, 5];
+        let seq = [0, 1, 2, 4, 5];
         assert_eq!(
             recipes.find_index_of_sequence(&seq[..]),
             5,
@@ -214,7 +247,7 @@ mod tests {
     #[test]
     fn finds_index_of_sequence_3() {
         let mut recipes = Recipes::new();
-        let seq = vec![9, 2, 5, 1, 0];
+        let seq = [9, 2, 5, 1, 0];
         assert_eq!(
             recipes.find_index_of_sequence(&seq[..]),
             18,
@@ -224,17 +257,27 @@ mod tests {
     #[test]
     fn finds_index_of_sequence_4() {
         let mut recipes = Recipes::new();
-        let seq = vec![5, 9, 4, 1, 4];
+        let seq = [5, 9, 4, 1, 4];
         assert_eq!(
             recipes.find_index_of_sequence(&seq[..]),
             2018,
         );
     }
 
+    #[test]
+    fn solves_part1_from_text_input() {
+        assert_eq!(solve_part1(&Input::text("2018\n")).unwrap(), "5941429882");
+    }
+
+    #[test]
+    fn solves_part2_from_text_input() {
+        assert_eq!(solve_part2(&Input::text("59414\n")).unwrap(), 2018);
+    }
+
     #[test]
     fn gets_digit_seq_1() {
         assert_eq!(
-            digit_seq("51589"),
+            digit_seq("51589").unwrap(),
             vec![5, 1, 5, 8, 9],
         );
     }
@@ -242,8 +285,19 @@ mod tests {
     #[test]
     fn gets_digit_seq_2() {
         assert_eq!(
-            digit_seq("01245"),
+            digit_seq("01245").unwrap(),
             vec![0, 1, 2, 4, 5],
         );
     }
+
+    #[test]
+    fn errors_on_non_digit_in_seq() {
+        match digit_seq("51x89") {
+            Ok(_) => panic!("digit_seq should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 1, column 3: Not a digit: 'x': \"51x89\"",
+            ),
+        }
+    }
 }
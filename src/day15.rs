@@ -0,0 +1,447 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/15.txt";
+const HIT_POINTS: i32 = 200;
+const GOBLIN_ATTACK_POWER: i32 = 3;
+
+/// Positions sort in reading order: top to bottom, then left to right.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Position {
+    pub y: usize,
+    pub x: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Race {
+    Elf,
+    Goblin,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Unit {
+    race: Race,
+    position: Position,
+    hit_points: i32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cave {
+    open: Vec<Vec<bool>>,
+    units: Vec<Unit>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Battle {
+    pub rounds: usize,
+    pub hit_points: i32,
+    pub elf_deaths: usize,
+}
+
+impl Position {
+    /// Adjacent positions in reading order.
+    fn neighbors(self) -> Vec<Position> {
+        let Position { x, y } = self;
+        let mut neighbors = Vec::with_capacity(4);
+        if y > 0 {
+            neighbors.push(Position { x, y: y - 1 });
+        }
+        if x > 0 {
+            neighbors.push(Position { x: x - 1, y });
+        }
+        neighbors.push(Position { x: x + 1, y });
+        neighbors.push(Position { x, y: y + 1 });
+        neighbors
+    }
+}
+
+impl Race {
+    fn symbol(self) -> char {
+        match self {
+            Race::Elf => 'E',
+            Race::Goblin => 'G',
+        }
+    }
+}
+
+impl Unit {
+    fn is_alive(&self) -> bool {
+        self.hit_points > 0
+    }
+}
+
+impl Battle {
+    pub fn outcome(&self) -> i32 {
+        self.rounds as i32 * self.hit_points
+    }
+}
+
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.open.iter().enumerate() {
+            let mut row_units = vec![];
+            for (x, open) in row.iter().enumerate() {
+                let position = Position { x, y };
+                match self.units.iter().find(|unit| unit.position == position) {
+                    Some(unit) => {
+                        write!(f, "{}", unit.race.symbol())?;
+                        row_units.push(format!("{}({})", unit.race.symbol(), unit.hit_points));
+                    }
+                    None if *open => write!(f, ".")?,
+                    None => write!(f, "#")?,
+                }
+            }
+            if !row_units.is_empty() {
+                write!(f, "   {}", row_units.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Cave {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Cave> {
+        let mut open = vec![];
+        let mut units = vec![];
+        for (y, line) in s.lines().enumerate() {
+            let mut row = vec![];
+            for (x, c) in line.chars().enumerate() {
+                let race = match c {
+                    '#' => None,
+                    '.' => None,
+                    'E' => Some(Race::Elf),
+                    'G' => Some(Race::Goblin),
+                    _ => {
+                        return Err(Error::parse(
+                            y + 1,
+                            line,
+                            format!("Unexpected character in cave map: {:?}", c),
+                        )
+                        .at_column(x + 1))
+                    }
+                };
+                if let Some(race) = race {
+                    units.push(Unit {
+                        race,
+                        position: Position { x, y },
+                        hit_points: HIT_POINTS,
+                    });
+                }
+                row.push(c != '#');
+            }
+            open.push(row);
+        }
+        Ok(Cave { open, units })
+    }
+}
+
+impl Cave {
+    fn is_open(&self, position: Position) -> bool {
+        self.open
+            .get(position.y)
+            .and_then(|row| row.get(position.x))
+            .cloned()
+            .unwrap_or(false)
+    }
+
+    fn occupied(&self) -> HashSet<Position> {
+        self.units
+            .iter()
+            .filter(|unit| unit.is_alive())
+            .map(|unit| unit.position)
+            .collect()
+    }
+
+    /// Breadth-first distances from `start` to every square reachable without passing through a
+    /// wall or an occupied square.
+    fn distances_from(
+        &self,
+        start: Position,
+        occupied: &HashSet<Position>,
+    ) -> HashMap<Position, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(start, 0);
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            let distance = distances[&position];
+            for neighbor in position.neighbors() {
+                if self.is_open(neighbor)
+                    && !occupied.contains(&neighbor)
+                    && !distances.contains_key(&neighbor)
+                {
+                    distances.insert(neighbor, distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        distances
+    }
+
+    fn is_enemy(&self, index: usize, other: &Unit) -> bool {
+        other.is_alive() && other.race != self.units[index].race
+    }
+
+    /// The square the unit should step to: the first step along the shortest path to the
+    /// nearest square in range of an enemy, with ties broken in reading order.
+    fn step_towards_enemy(&self, index: usize) -> Option<Position> {
+        let occupied = self.occupied();
+        let in_range: HashSet<Position> = self
+            .units
+            .iter()
+            .filter(|other| self.is_enemy(index, other))
+            .flat_map(|enemy| enemy.position.neighbors())
+            .filter(|position| self.is_open(*position) && !occupied.contains(position))
+            .collect();
+        let distances = self.distances_from(self.units[index].position, &occupied);
+        let (_, destination) = in_range
+            .iter()
+            .filter_map(|position| distances.get(position).map(|distance| (distance, position)))
+            .min()?;
+        let distances_back = self.distances_from(*destination, &occupied);
+        self.units[index]
+            .position
+            .neighbors()
+            .into_iter()
+            .filter_map(|position| {
+                distances_back
+                    .get(&position)
+                    .map(|distance| (*distance, position))
+            })
+            .min()
+            .map(|(_, position)| position)
+    }
+
+    /// The adjacent enemy with the fewest hit points, with ties broken in reading order.
+    fn weakest_adjacent_enemy(&self, index: usize) -> Option<usize> {
+        let neighbors = self.units[index].position.neighbors();
+        self.units
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| self.is_enemy(index, other) && neighbors.contains(&other.position))
+            .min_by_key(|(_, enemy)| (enemy.hit_points, enemy.position))
+            .map(|(enemy_index, _)| enemy_index)
+    }
+
+    /// Runs a round of combat, returning whether it was completed. A round ends early when a
+    /// unit starts its turn with no enemies left, along with the combat. `acted` is set if any
+    /// unit moved or attacked.
+    fn run_round(
+        &mut self,
+        elf_attack_power: i32,
+        elf_deaths: &mut usize,
+        acted: &mut bool,
+    ) -> bool {
+        self.units.sort_unstable_by_key(|unit| unit.position);
+        for index in 0..self.units.len() {
+            if !self.units[index].is_alive() {
+                continue;
+            }
+            if !self.units.iter().any(|other| self.is_enemy(index, other)) {
+                self.units.retain(Unit::is_alive);
+                return false;
+            }
+
+            if self.weakest_adjacent_enemy(index).is_none() {
+                if let Some(step) = self.step_towards_enemy(index) {
+                    self.units[index].position = step;
+                    *acted = true;
+                }
+            }
+
+            if let Some(enemy_index) = self.weakest_adjacent_enemy(index) {
+                let attack_power = match self.units[index].race {
+                    Race::Elf => elf_attack_power,
+                    Race::Goblin => GOBLIN_ATTACK_POWER,
+                };
+                let enemy = &mut self.units[enemy_index];
+                enemy.hit_points -= attack_power;
+                if !enemy.is_alive() && enemy.race == Race::Elf {
+                    *elf_deaths += 1;
+                }
+                *acted = true;
+            }
+        }
+        self.units.retain(Unit::is_alive);
+        true
+    }
+
+    /// Fights until one side is left standing, or until the first elf dies if
+    /// `stop_at_elf_death` is set.
+    pub fn fight(&mut self, elf_attack_power: i32, stop_at_elf_death: bool) -> Result<Battle> {
+        let mut rounds = 0;
+        let mut elf_deaths = 0;
+        loop {
+            let mut acted = false;
+            if !self.run_round(elf_attack_power, &mut elf_deaths, &mut acted) {
+                break;
+            }
+            if stop_at_elf_death && elf_deaths > 0 {
+                break;
+            }
+            if !acted {
+                return Err(Error::simulation(format!(
+                    "Combat stalled after {} rounds, the remaining units cannot reach each other",
+                    rounds
+                )));
+            }
+            rounds += 1;
+        }
+        Ok(Battle {
+            rounds,
+            hit_points: self.units.iter().map(|unit| unit.hit_points).sum(),
+            elf_deaths,
+        })
+    }
+
+    /// Finds the battle won by the elves with the lowest attack power that loses no elves.
+    pub fn fight_with_no_elf_deaths(&self) -> Result<Battle> {
+        for elf_attack_power in GOBLIN_ATTACK_POWER + 1..=HIT_POINTS {
+            let battle = self.clone().fight(elf_attack_power, true)?;
+            if battle.elf_deaths == 0 {
+                return Ok(battle);
+            }
+        }
+        Err(Error::simulation(format!(
+            "An elf dies even with an attack power of {}",
+            HIT_POINTS
+        )))
+    }
+}
+
+pub fn read_cave(input: &Input) -> Result<Cave> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<i32> {
+    let mut cave = read_cave(input)?;
+    Ok(cave.fight(GOBLIN_ATTACK_POWER, false)?.outcome())
+}
+
+pub fn solve_part2(input: &Input) -> Result<i32> {
+    let cave = read_cave(input)?;
+    Ok(cave.fight_with_no_elf_deaths()?.outcome())
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Cave;
+
+    fn parse(input: &Input) -> Result<Cave> {
+        read_cave(input)
+    }
+
+    fn part1(cave: &Cave) -> Result<Answer> {
+        Ok(cave
+            .clone()
+            .fight(GOBLIN_ATTACK_POWER, false)?
+            .outcome()
+            .into())
+    }
+
+    fn part2(cave: &Cave) -> Result<Answer> {
+        Ok(cave.fight_with_no_elf_deaths()?.outcome().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/15_test.txt";
+    const TEST_INPUT_2: &str = "inputs/15_test_2.txt";
+    const TEST_INPUT_3: &str = "inputs/15_test_3.txt";
+    const TEST_INPUT_4: &str = "inputs/15_test_4.txt";
+    const TEST_INPUT_5: &str = "inputs/15_test_5.txt";
+
+    #[test]
+    fn reads_cave_file() {
+        let cave = read_cave(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(cave.units.len(), 6);
+        assert_eq!(
+            cave.units[0],
+            Unit {
+                race: Race::Goblin,
+                position: Position { x: 2, y: 1 },
+                hit_points: HIT_POINTS,
+            }
+        );
+        assert_eq!(
+            cave.to_string(),
+            "#######\n\
+             #.G...#   G(200)\n\
+             #...EG#   E(200), G(200)\n\
+             #.#.#G#   G(200)\n\
+             #..G#E#   G(200), E(200)\n\
+             #.....#\n\
+             #######\n"
+        );
+    }
+
+    #[test]
+    fn moves_towards_nearest_enemy_in_reading_order() {
+        let mut cave: Cave = "#######\n#E..G.#\n#...#.#\n#.G.#G#\n#######"
+            .parse()
+            .unwrap();
+        cave.run_round(GOBLIN_ATTACK_POWER, &mut 0, &mut false);
+        assert_eq!(cave.units[0].position, Position { x: 2, y: 1 });
+    }
+
+    #[test]
+    fn fights_to_the_end() {
+        let mut cave = read_cave(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(
+            cave.fight(GOBLIN_ATTACK_POWER, false).unwrap(),
+            Battle {
+                rounds: 47,
+                hit_points: 590,
+                elf_deaths: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 27730);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_2)).unwrap(), 39514);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_3)).unwrap(), 27755);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_4)).unwrap(), 28944);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_5)).unwrap(), 18740);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT)).unwrap(), 4988);
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT_2)).unwrap(), 31284);
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT_3)).unwrap(), 3478);
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT_4)).unwrap(), 6474);
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT_5)).unwrap(), 1140);
+    }
+
+    #[test]
+    fn errors_on_unexpected_character() {
+        match read_cave(&Input::text("#####\n#E.G#\n#.x.#\n#####\n")) {
+            Ok(_) => panic!("read_cave should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 3, column 3: Unexpected character in cave map: 'x': \"#.x.#\""
+            ),
+        }
+    }
+
+    #[test]
+    fn errors_when_combat_stalls() {
+        let mut cave: Cave = "#######\n#E.#.G#\n#######".parse().unwrap();
+        assert!(cave.fight(GOBLIN_ATTACK_POWER, false).is_err());
+    }
+}
@@ -0,0 +1,483 @@
+extern crate regex;
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::error::{parse_field, Error, ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/16.txt";
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+pub const OPCODES: [Opcode; 16] = [
+    Opcode::Addr,
+    Opcode::Addi,
+    Opcode::Mulr,
+    Opcode::Muli,
+    Opcode::Banr,
+    Opcode::Bani,
+    Opcode::Borr,
+    Opcode::Bori,
+    Opcode::Setr,
+    Opcode::Seti,
+    Opcode::Gtir,
+    Opcode::Gtri,
+    Opcode::Gtrr,
+    Opcode::Eqir,
+    Opcode::Eqri,
+    Opcode::Eqrr,
+];
+
+/// An instruction for the device, where the opcode is either known or still just a number.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Instruction<O> {
+    pub opcode: O,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+pub type Registers = [usize; 4];
+
+#[derive(Debug, PartialEq)]
+pub struct Sample {
+    before: Registers,
+    instruction: Instruction<usize>,
+    after: Registers,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Manual {
+    samples: Vec<Sample>,
+    program: Vec<Instruction<usize>>,
+}
+
+fn read_register(registers: &[usize], register: usize) -> Result<usize> {
+    registers
+        .get(register)
+        .cloned()
+        .ok_or_else(|| Error::simulation(format!("Register {} does not exist", register)))
+}
+
+fn overflow<T>(value: Option<T>) -> Result<T> {
+    value.ok_or_else(|| Error::simulation("Register value overflowed"))
+}
+
+impl Opcode {
+    /// Runs the opcode on `registers`, failing if it reads or writes a register that doesn't
+    /// exist.
+    pub fn execute(self, a: usize, b: usize, c: usize, registers: &mut [usize]) -> Result<()> {
+        let reg = |register| read_register(registers, register);
+        let value = match self {
+            Opcode::Addr => overflow(reg(a)?.checked_add(reg(b)?))?,
+            Opcode::Addi => overflow(reg(a)?.checked_add(b))?,
+            Opcode::Mulr => overflow(reg(a)?.checked_mul(reg(b)?))?,
+            Opcode::Muli => overflow(reg(a)?.checked_mul(b))?,
+            Opcode::Banr => reg(a)? & reg(b)?,
+            Opcode::Bani => reg(a)? & b,
+            Opcode::Borr => reg(a)? | reg(b)?,
+            Opcode::Bori => reg(a)? | b,
+            Opcode::Setr => reg(a)?,
+            Opcode::Seti => a,
+            Opcode::Gtir => (a > reg(b)?) as usize,
+            Opcode::Gtri => (reg(a)? > b) as usize,
+            Opcode::Gtrr => (reg(a)? > reg(b)?) as usize,
+            Opcode::Eqir => (a == reg(b)?) as usize,
+            Opcode::Eqri => (reg(a)? == b) as usize,
+            Opcode::Eqrr => (reg(a)? == reg(b)?) as usize,
+        };
+        read_register(registers, c)?;
+        registers[c] = value;
+        Ok(())
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Opcode> {
+        match s {
+            "addr" => Ok(Opcode::Addr),
+            "addi" => Ok(Opcode::Addi),
+            "mulr" => Ok(Opcode::Mulr),
+            "muli" => Ok(Opcode::Muli),
+            "banr" => Ok(Opcode::Banr),
+            "bani" => Ok(Opcode::Bani),
+            "borr" => Ok(Opcode::Borr),
+            "bori" => Ok(Opcode::Bori),
+            "setr" => Ok(Opcode::Setr),
+            "seti" => Ok(Opcode::Seti),
+            "gtir" => Ok(Opcode::Gtir),
+            "gtri" => Ok(Opcode::Gtri),
+            "gtrr" => Ok(Opcode::Gtrr),
+            "eqir" => Ok(Opcode::Eqir),
+            "eqri" => Ok(Opcode::Eqri),
+            "eqrr" => Ok(Opcode::Eqrr),
+            _ => Err(Error::parse(1, s, "Unknown opcode")),
+        }
+    }
+}
+
+impl Instruction<Opcode> {
+    pub fn execute(&self, registers: &mut [usize]) -> Result<()> {
+        self.opcode.execute(self.a, self.b, self.c, registers)
+    }
+}
+
+/// Parses an instruction written with the opcode's name, like `addi 2 16 2`.
+impl FromStr for Instruction<Opcode> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Instruction<Opcode>> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        match fields.as_slice() {
+            [opcode, a, b, c] => Ok(Instruction {
+                opcode: opcode.parse().map_err(|_| {
+                    Error::parse(1, s, format!("Unknown opcode {:?}", opcode))
+                        .at_column(s.find(opcode).unwrap() + 1)
+                })?,
+                a: a.parse().on_line(1, s)?,
+                b: b.parse().on_line(1, s)?,
+                c: c.parse().on_line(1, s)?,
+            }),
+            _ => Err(Error::parse(
+                1,
+                s,
+                "Malformed instruction, expected an opcode and three operands",
+            )),
+        }
+    }
+}
+
+impl FromStr for Instruction<usize> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Instruction<usize>> {
+        let values = s
+            .split_whitespace()
+            .map(|value| value.parse().on_line(1, s))
+            .collect::<Result<Vec<usize>>>()?;
+        match values.as_slice() {
+            [opcode, a, b, c] => Ok(Instruction {
+                opcode: *opcode,
+                a: *a,
+                b: *b,
+                c: *c,
+            }),
+            _ => Err(Error::parse(
+                1,
+                s,
+                "Malformed instruction, expected an opcode and three operands",
+            )),
+        }
+    }
+}
+
+fn parse_registers(s: &str, label: &str) -> Result<Registers> {
+    lazy_static! {
+        static ref REGISTERS_REGEX: Regex = Regex::new(
+            r"^(?P<label>Before|After):\s+\[(?P<r0>\d+), (?P<r1>\d+), (?P<r2>\d+), (?P<r3>\d+)\]$"
+        )
+        .unwrap();
+    }
+    match REGISTERS_REGEX.captures(s) {
+        Some(ref captures) if &captures["label"] == label => Ok([
+            parse_field(captures, "r0", s)?,
+            parse_field(captures, "r1", s)?,
+            parse_field(captures, "r2", s)?,
+            parse_field(captures, "r3", s)?,
+        ]),
+        _ => Err(Error::parse(
+            1,
+            s,
+            format!(
+                "Malformed sample, expected the registers {}",
+                label.to_lowercase()
+            ),
+        )),
+    }
+}
+
+impl Sample {
+    pub fn matching_opcodes(&self) -> Vec<Opcode> {
+        OPCODES
+            .iter()
+            .cloned()
+            .filter(|opcode| {
+                let mut registers = self.before;
+                let Instruction { a, b, c, .. } = self.instruction;
+                opcode.execute(a, b, c, &mut registers).is_ok() && registers == self.after
+            })
+            .collect()
+    }
+}
+
+pub fn read_manual(input: &Input) -> Result<Manual> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+impl FromStr for Manual {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Manual> {
+        let mut samples = vec![];
+        let mut program = vec![];
+        let mut lines = s.lines().enumerate();
+        while let Some((index, line)) = lines.next() {
+            let at_line = |line_number: usize| move |err: Error| err.at_line(line_number + 1);
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.starts_with("Before:") {
+                let before = parse_registers(line, "Before").map_err(at_line(index))?;
+                let (index, line) = lines.next().unwrap_or((index + 1, ""));
+                let instruction = line.parse().map_err(at_line(index))?;
+                let (index, line) = lines.next().unwrap_or((index + 1, ""));
+                let after = parse_registers(line, "After").map_err(at_line(index))?;
+                samples.push(Sample {
+                    before,
+                    instruction,
+                    after,
+                });
+            } else {
+                program.push(line.parse().map_err(at_line(index))?);
+            }
+        }
+        Ok(Manual { samples, program })
+    }
+}
+
+impl Manual {
+    pub fn count_ambiguous_samples(&self) -> usize {
+        self.samples
+            .iter()
+            .filter(|sample| sample.matching_opcodes().len() >= 3)
+            .count()
+    }
+
+    /// Works out which opcode each number stands for by narrowing each number down to the
+    /// opcodes that match all of its samples, then repeatedly removing any opcode that is the
+    /// only candidate left for some number from every other number.
+    pub fn deduce_opcodes(&self) -> Result<HashMap<usize, Opcode>> {
+        let mut candidates: HashMap<usize, HashSet<Opcode>> = HashMap::new();
+        for sample in self.samples.iter() {
+            let matching: HashSet<Opcode> = sample.matching_opcodes().into_iter().collect();
+            let number = sample.instruction.opcode;
+            let number_candidates = candidates
+                .entry(number)
+                .or_insert_with(|| OPCODES.iter().cloned().collect());
+            number_candidates.retain(|opcode| matching.contains(opcode));
+            if number_candidates.is_empty() {
+                return Err(Error::simulation(format!(
+                    "Contradictory samples, no opcode matches every sample for opcode number {}",
+                    number
+                )));
+            }
+        }
+
+        let mut opcodes = HashMap::new();
+        while !candidates.is_empty() {
+            let solved: Vec<(usize, Opcode)> = candidates
+                .iter()
+                .filter(|(_, opcodes)| opcodes.len() == 1)
+                .map(|(number, opcodes)| (*number, *opcodes.iter().next().unwrap()))
+                .collect();
+            if solved.is_empty() {
+                let mut unsolved: Vec<usize> = candidates.keys().cloned().collect();
+                unsolved.sort_unstable();
+                return Err(Error::simulation(format!(
+                    "Ambiguous samples, opcode numbers {:?} could each be more than one opcode",
+                    unsolved
+                )));
+            }
+            for (number, opcode) in solved {
+                candidates.remove(&number);
+                for (other_number, other_opcodes) in candidates.iter_mut() {
+                    other_opcodes.remove(&opcode);
+                    if other_opcodes.is_empty() {
+                        return Err(Error::simulation(format!(
+                            "Contradictory samples, opcode numbers {} and {} must both be {:?}",
+                            number, other_number, opcode
+                        )));
+                    }
+                }
+                opcodes.insert(number, opcode);
+            }
+        }
+        Ok(opcodes)
+    }
+
+    pub fn run_program(&self) -> Result<Registers> {
+        let opcodes = self.deduce_opcodes()?;
+        let mut registers = [0; 4];
+        for instruction in self.program.iter() {
+            let opcode = opcodes.get(&instruction.opcode).ok_or_else(|| {
+                Error::simulation(format!(
+                    "Opcode number {} does not appear in any sample",
+                    instruction.opcode
+                ))
+            })?;
+            Instruction {
+                opcode: *opcode,
+                a: instruction.a,
+                b: instruction.b,
+                c: instruction.c,
+            }
+            .execute(&mut registers)?;
+        }
+        Ok(registers)
+    }
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    Ok(read_manual(input)?.count_ambiguous_samples())
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    Ok(read_manual(input)?.run_program()?[0])
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Manual;
+
+    fn parse(input: &Input) -> Result<Manual> {
+        read_manual(input)
+    }
+
+    fn part1(manual: &Manual) -> Result<Answer> {
+        Ok(manual.count_ambiguous_samples().into())
+    }
+
+    fn part2(manual: &Manual) -> Result<Answer> {
+        Ok(manual.run_program()?[0].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/16_test.txt";
+    const TEST_INPUT_PART_2: &str = "inputs/16_test_part2.txt";
+
+    fn test_sample() -> Sample {
+        Sample {
+            before: [3, 2, 1, 1],
+            instruction: Instruction {
+                opcode: 9,
+                a: 2,
+                b: 1,
+                c: 2,
+            },
+            after: [3, 2, 2, 1],
+        }
+    }
+
+    #[test]
+    fn reads_manual_file() {
+        assert_eq!(
+            read_manual(&Input::file(TEST_INPUT)).unwrap(),
+            Manual {
+                samples: vec![test_sample()],
+                program: vec![Instruction {
+                    opcode: 9,
+                    a: 2,
+                    b: 1,
+                    c: 2,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn matches_sample_opcodes() {
+        assert_eq!(
+            test_sample().matching_opcodes(),
+            vec![Opcode::Addi, Opcode::Mulr, Opcode::Seti]
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 1);
+    }
+
+    #[test]
+    fn deduces_opcodes() {
+        // Every opcode is numbered in reverse, and the program is seti 7 then muli by 7.
+        let manual = read_manual(&Input::file(TEST_INPUT_PART_2)).unwrap();
+        let opcodes = manual.deduce_opcodes().unwrap();
+        for (index, opcode) in OPCODES.iter().enumerate() {
+            assert_eq!(opcodes[&(15 - index)], *opcode);
+        }
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT_PART_2)).unwrap(), 49);
+    }
+
+    #[test]
+    fn errors_on_ambiguous_samples() {
+        let manual = read_manual(&Input::file(TEST_INPUT)).unwrap();
+        match manual.run_program() {
+            Err(Error::Simulation(message)) => assert_eq!(
+                message,
+                "Ambiguous samples, opcode numbers [9] could each be more than one opcode"
+            ),
+            result => panic!("expected a simulation error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn errors_on_contradictory_samples() {
+        let mut samples = vec![test_sample()];
+        samples.push(Sample {
+            after: [3, 2, 1, 1],
+            ..test_sample()
+        });
+        let manual = Manual {
+            samples,
+            program: vec![],
+        };
+        assert!(manual.deduce_opcodes().is_err());
+    }
+
+    #[test]
+    fn errors_on_malformed_sample() {
+        match read_manual(&Input::text(
+            "Before: [3, 2, 1, 1]\n9 2 1\nAfter:  [3, 2, 2, 1]\n",
+        )) {
+            Ok(_) => panic!("read_manual should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Malformed instruction, expected an opcode and three operands: \"9 2 1\""
+            ),
+        }
+    }
+}
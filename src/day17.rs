@@ -0,0 +1,382 @@
+extern crate regex;
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/17.txt";
+const SPRING_X: usize = 500;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vein {
+    x: RangeInclusive<usize>,
+    y: RangeInclusive<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Tile {
+    Sand,
+    Clay,
+    Flowing,
+    Settled,
+}
+
+/// A vertical slice of the ground, with a column of sand on either side of the clay so that
+/// water can flow past the outermost veins. Tiles are indexed by column rather than by x, with
+/// column 0 being the sand to the left of the leftmost vein, which can be left of x=0.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ground {
+    tiles: Vec<Vec<Tile>>,
+    spring: usize,
+    min_y: usize,
+}
+
+impl FromStr for Vein {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Vein> {
+        lazy_static! {
+            static ref VEIN_REGEX: Regex = Regex::new(concat!(
+                r"^(?P<axis>[xy])=(?P<at>\d+), ",
+                r"(?P<range_axis>[xy])=(?P<start>\d+)\.\.(?P<end>\d+)$"
+            ))
+            .unwrap();
+        }
+        let captures = match VEIN_REGEX.captures(s) {
+            Some(captures) => captures,
+            None => {
+                return Err(Error::parse(
+                    1,
+                    s,
+                    "Malformed vein line, expected x=<x>, y=<y>..<y> or y=<y>, x=<x>..<x>",
+                ))
+            }
+        };
+        let at: usize = parse_field(&captures, "at", s)?;
+        let start: usize = parse_field(&captures, "start", s)?;
+        let end: usize = parse_field(&captures, "end", s)?;
+        if start > end {
+            return Err(
+                Error::parse(1, s, "Malformed vein line, range ends before it starts")
+                    .at_column(captures.name("start").unwrap().start() + 1),
+            );
+        }
+        match (&captures["axis"], &captures["range_axis"]) {
+            ("x", "y") => Ok(Vein {
+                x: at..=at,
+                y: start..=end,
+            }),
+            ("y", "x") => Ok(Vein {
+                x: start..=end,
+                y: at..=at,
+            }),
+            _ => Err(Error::parse(
+                1,
+                s,
+                "Malformed vein line, the same axis is given twice",
+            )),
+        }
+    }
+}
+
+impl FromStr for Ground {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Ground> {
+        let veins = s
+            .lines()
+            .enumerate()
+            .map(|(index, line)| line.parse().map_err(|err: Error| err.at_line(index + 1)))
+            .collect::<Result<Vec<Vein>>>()?;
+        if veins.is_empty() {
+            return Err(Error::parse(1, "", "No clay veins in the scan"));
+        }
+        Ok(Ground::new(&veins))
+    }
+}
+
+impl fmt::Display for Ground {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let symbol = match tile {
+                    _ if y == 0 && x == self.spring => '+',
+                    Tile::Sand => '.',
+                    Tile::Clay => '#',
+                    Tile::Flowing => '|',
+                    Tile::Settled => '~',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ground {
+    pub fn new(veins: &[Vein]) -> Ground {
+        let min_x = veins
+            .iter()
+            .map(|vein| *vein.x.start())
+            .min()
+            .unwrap_or(SPRING_X);
+        let max_x = veins
+            .iter()
+            .map(|vein| *vein.x.end())
+            .max()
+            .unwrap_or(SPRING_X);
+        let min_y = veins.iter().map(|vein| *vein.y.start()).min().unwrap_or(0);
+        let max_y = veins.iter().map(|vein| *vein.y.end()).max().unwrap_or(0);
+        let left = min_x.min(SPRING_X);
+        let right = max_x.max(SPRING_X);
+        let column = |x: usize| x - left + 1;
+
+        let mut tiles = vec![vec![Tile::Sand; column(right) + 2]; max_y + 1];
+        for vein in veins {
+            for y in vein.y.clone() {
+                for x in vein.x.clone() {
+                    tiles[y][column(x)] = Tile::Clay;
+                }
+            }
+        }
+        Ground {
+            tiles,
+            spring: column(SPRING_X),
+            min_y,
+        }
+    }
+
+    fn tile(&self, x: usize, y: usize) -> Tile {
+        self.tiles[y][x]
+    }
+
+    fn set_tile(&mut self, x: usize, y: usize, tile: Tile) {
+        self.tiles[y][x] = tile;
+    }
+
+    fn holds_water(&self, x: usize, y: usize) -> bool {
+        match self.tile(x, y) {
+            Tile::Clay | Tile::Settled => true,
+            Tile::Sand | Tile::Flowing => false,
+        }
+    }
+
+    /// Follows water sideways from `x` along row `y` until it either hits a wall or reaches a
+    /// tile with nothing underneath to hold it up. Returns the last tile reached and whether it
+    /// is against a wall.
+    fn spread(&self, x: usize, y: usize, left: bool) -> (usize, bool) {
+        let mut x = x;
+        loop {
+            if !self.holds_water(x, y + 1) {
+                return (x, false);
+            }
+            let next_x = if left { x - 1 } else { x + 1 };
+            if self.tile(next_x, y) == Tile::Clay {
+                return (x, true);
+            }
+            x = next_x;
+        }
+    }
+
+    /// Lets the water run from the spring until every reachable tile is wet. Water that falls off
+    /// a ledge is pushed onto a stack of sources rather than recursed into, so that deep or wide
+    /// scans can't overflow the call stack.
+    pub fn flow(&mut self) {
+        let bottom = self.tiles.len() - 1;
+        let mut sources = vec![(self.spring, 0)];
+        while let Some((x, mut y)) = sources.pop() {
+            while y < bottom && self.tile(x, y + 1) == Tile::Sand {
+                self.set_tile(x, y, Tile::Flowing);
+                y += 1;
+            }
+            self.set_tile(x, y, Tile::Flowing);
+            if y == bottom || self.tile(x, y + 1) == Tile::Flowing {
+                continue;
+            }
+
+            loop {
+                let (left, left_walled) = self.spread(x, y, true);
+                let (right, right_walled) = self.spread(x, y, false);
+                if left_walled && right_walled && y > 0 {
+                    for wet_x in left..=right {
+                        self.set_tile(wet_x, y, Tile::Settled);
+                    }
+                    y -= 1;
+                } else {
+                    for wet_x in left..=right {
+                        self.set_tile(wet_x, y, Tile::Flowing);
+                    }
+                    if !left_walled {
+                        sources.push((left, y));
+                    }
+                    if !right_walled {
+                        sources.push((right, y));
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Counts the tiles matching `wet` between the highest and lowest clay.
+    fn count_tiles(&self, wet: fn(Tile) -> bool) -> usize {
+        self.tiles[self.min_y..]
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|tile| wet(**tile))
+            .count()
+    }
+
+    pub fn count_reachable(&self) -> usize {
+        self.count_tiles(|tile| tile == Tile::Flowing || tile == Tile::Settled)
+    }
+
+    pub fn count_retained(&self) -> usize {
+        self.count_tiles(|tile| tile == Tile::Settled)
+    }
+}
+
+pub fn read_ground(input: &Input) -> Result<Ground> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    let mut ground = read_ground(input)?;
+    ground.flow();
+    Ok(ground.count_reachable())
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    let mut ground = read_ground(input)?;
+    ground.flow();
+    Ok(ground.count_retained())
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = Ground;
+
+    fn parse(input: &Input) -> Result<Ground> {
+        read_ground(input)
+    }
+
+    fn part1(ground: &Ground) -> Result<Answer> {
+        let mut ground = ground.clone();
+        ground.flow();
+        Ok(ground.count_reachable().into())
+    }
+
+    fn part2(ground: &Ground) -> Result<Answer> {
+        let mut ground = ground.clone();
+        ground.flow();
+        Ok(ground.count_retained().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/17_test.txt";
+
+    #[test]
+    fn reads_veins() {
+        assert_eq!(
+            "x=495, y=2..7".parse::<Vein>().unwrap(),
+            Vein {
+                x: 495..=495,
+                y: 2..=7,
+            }
+        );
+        assert_eq!(
+            "y=7, x=495..501".parse::<Vein>().unwrap(),
+            Vein {
+                x: 495..=501,
+                y: 7..=7,
+            }
+        );
+    }
+
+    #[test]
+    fn renders_ground_after_flowing() {
+        let mut ground = read_ground(&Input::file(TEST_INPUT)).unwrap();
+        ground.flow();
+        assert_eq!(
+            ground.to_string(),
+            "......+.......\n\
+             ......|.....#.\n\
+             .#..#||||...#.\n\
+             .#..#~~#|.....\n\
+             .#..#~~#|.....\n\
+             .#~~~~~#|.....\n\
+             .#~~~~~#|.....\n\
+             .#######|.....\n\
+             ........|.....\n\
+             ...|||||||||..\n\
+             ...|#~~~~~#|..\n\
+             ...|#~~~~~#|..\n\
+             ...|#~~~~~#|..\n\
+             ...|#######|..\n"
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 57);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT)).unwrap(), 29);
+    }
+
+    #[test]
+    fn fills_nested_basins_from_both_sides() {
+        let mut ground: Ground = "x=495, y=2..10\nx=505, y=2..10\ny=10, x=495..505\nx=499, y=5..7\nx=501, y=5..7\ny=7, x=499..501"
+            .parse()
+            .unwrap();
+        ground.flow();
+        // Water lands in the inner box first, then spills off both sides of it and fills the
+        // outer basin from the bottom up to its brim.
+        assert_eq!(ground.count_retained(), 65);
+    }
+
+    #[test]
+    fn flows_down_tall_scans_without_recursing() {
+        let mut ground: Ground = "x=499, y=1..100000".parse().unwrap();
+        ground.flow();
+        assert_eq!(ground.count_reachable(), 100000);
+        assert_eq!(ground.count_retained(), 0);
+    }
+
+    #[test]
+    fn errors_on_malformed_vein() {
+        match read_ground(&Input::text("x=495, y=2..7\nx=495, x=2..7\n")) {
+            Ok(_) => panic!("read_ground should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Malformed vein line, the same axis is given twice: \"x=495, x=2..7\""
+            ),
+        }
+        assert!("y=7, x=501..495".parse::<Vein>().is_err());
+    }
+
+    #[test]
+    fn flows_past_clay_at_x_0() {
+        let mut ground: Ground = "y=4, x=0..600".parse().unwrap();
+        ground.flow();
+        // Water spreads along the top of the vein and falls off both ends, reaching the lowest
+        // row only beside it, including in the column left of x=0.
+        assert_eq!(ground.count_reachable(), 2);
+        assert_eq!(ground.count_retained(), 0);
+    }
+}
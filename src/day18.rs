@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/18.txt";
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Acre {
+    Open,
+    Trees,
+    Lumberyard,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Landscape {
+    acres: Vec<Acre>,
+    width: usize,
+}
+
+impl fmt::Display for Landscape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.acres.chunks(self.width) {
+            for acre in row {
+                let symbol = match acre {
+                    Acre::Open => '.',
+                    Acre::Trees => '|',
+                    Acre::Lumberyard => '#',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Landscape {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Landscape> {
+        let mut acres = vec![];
+        let mut width = None;
+        for (index, line) in s.lines().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                acres.push(match c {
+                    '.' => Acre::Open,
+                    '|' => Acre::Trees,
+                    '#' => Acre::Lumberyard,
+                    _ => {
+                        return Err(Error::parse(
+                            index + 1,
+                            line,
+                            format!("Unexpected character in landscape: {:?}", c),
+                        )
+                        .at_column(column + 1))
+                    }
+                });
+            }
+            let line_width = line.chars().count();
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(Error::parse(
+                        index + 1,
+                        line,
+                        format!("Row is {} acres wide, expected {}", line_width, width),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Landscape { acres, width }),
+            _ => Err(Error::parse(1, "", "Landscape is empty")),
+        }
+    }
+}
+
+impl Landscape {
+    fn height(&self) -> usize {
+        self.acres.len() / self.width
+    }
+
+    /// Counts the trees and lumberyards among the (up to eight) acres around `x`, `y`.
+    fn count_adjacent(&self, x: usize, y: usize) -> (usize, usize) {
+        let (mut trees, mut lumberyards) = (0, 0);
+        for adjacent_y in y.saturating_sub(1)..=(y + 1).min(self.height() - 1) {
+            for adjacent_x in x.saturating_sub(1)..=(x + 1).min(self.width - 1) {
+                if (adjacent_x, adjacent_y) == (x, y) {
+                    continue;
+                }
+                match self.acres[adjacent_y * self.width + adjacent_x] {
+                    Acre::Trees => trees += 1,
+                    Acre::Lumberyard => lumberyards += 1,
+                    Acre::Open => {}
+                }
+            }
+        }
+        (trees, lumberyards)
+    }
+
+    pub fn next_minute(&self) -> Landscape {
+        let acres = self
+            .acres
+            .iter()
+            .enumerate()
+            .map(|(index, acre)| {
+                let (trees, lumberyards) =
+                    self.count_adjacent(index % self.width, index / self.width);
+                match acre {
+                    Acre::Open if trees >= 3 => Acre::Trees,
+                    Acre::Trees if lumberyards >= 3 => Acre::Lumberyard,
+                    Acre::Lumberyard if trees == 0 || lumberyards == 0 => Acre::Open,
+                    acre => *acre,
+                }
+            })
+            .collect();
+        Landscape {
+            acres,
+            width: self.width,
+        }
+    }
+
+    /// The landscape after `minutes` minutes. Once a landscape repeats, the rest of the minutes
+    /// just go round the same cycle, so the answer is looked up from the landscapes already seen.
+    pub fn after(&self, minutes: usize) -> Landscape {
+        let mut history = vec![self.clone()];
+        let mut seen = HashMap::new();
+        seen.insert(self.clone(), 0);
+        for minute in 1..=minutes {
+            let landscape = history[minute - 1].next_minute();
+            if let Some(&cycle_start) = seen.get(&landscape) {
+                let cycle_length = minute - cycle_start;
+                return history[cycle_start + (minutes - cycle_start) % cycle_length].clone();
+            }
+            seen.insert(landscape.clone(), minute);
+            history.push(landscape);
+        }
+        history.pop().unwrap()
+    }
+
+    pub fn resource_value(&self) -> usize {
+        let count = |kind| self.acres.iter().filter(|acre| **acre == kind).count();
+        count(Acre::Trees) * count(Acre::Lumberyard)
+    }
+}
+
+pub fn read_landscape(input: &Input) -> Result<Landscape> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    Ok(read_landscape(input)?.after(10).resource_value())
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    Ok(read_landscape(input)?.after(1_000_000_000).resource_value())
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = Landscape;
+
+    fn parse(input: &Input) -> Result<Landscape> {
+        read_landscape(input)
+    }
+
+    fn part1(landscape: &Landscape) -> Result<Answer> {
+        Ok(landscape.after(10).resource_value().into())
+    }
+
+    fn part2(landscape: &Landscape) -> Result<Answer> {
+        Ok(landscape.after(1_000_000_000).resource_value().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/18_test.txt";
+
+    #[test]
+    fn reads_landscape_file() {
+        let landscape = read_landscape(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(landscape.width, 10);
+        assert_eq!(landscape.height(), 10);
+        assert_eq!(
+            landscape.to_string(),
+            Input::file(TEST_INPUT).read_to_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn changes_over_a_minute() {
+        let landscape = read_landscape(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(
+            landscape.next_minute().to_string(),
+            ".......##.\n\
+             ......|###\n\
+             .|..|...#.\n\
+             ..|#||...#\n\
+             ..##||.|#|\n\
+             ...#||||..\n\
+             ||...|||..\n\
+             |||||.||.|\n\
+             ||||||||||\n\
+             ....||..|.\n"
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 1147);
+    }
+
+    #[test]
+    fn skips_ahead_through_cycles() {
+        let landscape = read_landscape(&Input::file(TEST_INPUT)).unwrap();
+        let mut stepped = landscape.clone();
+        for minutes in 1..=100 {
+            stepped = stepped.next_minute();
+            assert_eq!(landscape.after(minutes), stepped);
+        }
+    }
+
+    #[test]
+    fn errors_on_ragged_rows() {
+        match read_landscape(&Input::text("..|\n.|\n")) {
+            Ok(_) => panic!("read_landscape should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Row is 2 acres wide, expected 3: \".|\""
+            ),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::str::FromStr;
+
+use crate::day16::{Instruction, Opcode};
+use crate::error::{Error, ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/19.txt";
+const REGISTERS: usize = 6;
+const SETUP_STEP_LIMIT: usize = 1_000_000;
+
+pub type Registers = [usize; REGISTERS];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Program {
+    ip_register: usize,
+    instructions: Vec<Instruction<Opcode>>,
+}
+
+/// The program's hot loop, which tries every pair of factors up to the number in the `target`
+/// register and adds one of each pair that multiplies to it to register 0.
+#[derive(Debug, PartialEq)]
+pub struct DivisorLoop {
+    pub start: usize,
+    pub target: usize,
+}
+
+impl FromStr for Program {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Program> {
+        let mut lines = s.lines();
+        let first_line = lines.next().unwrap_or("");
+        let ip_register = match first_line.trim().splitn(2, ' ').collect::<Vec<&str>>()[..] {
+            ["#ip", register] => register.parse().on_line(1, first_line)?,
+            _ => {
+                return Err(Error::parse(
+                    1,
+                    first_line,
+                    "Malformed program, expected it to start with #ip <register>",
+                ))
+            }
+        };
+        if ip_register >= REGISTERS {
+            return Err(Error::parse(
+                1,
+                first_line,
+                format!("Register {} does not exist", ip_register),
+            ));
+        }
+        let instructions = lines
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| line.parse().map_err(|err: Error| err.at_line(index + 2)))
+            .collect::<Result<Vec<Instruction<Opcode>>>>()?;
+        Ok(Program {
+            ip_register,
+            instructions,
+        })
+    }
+}
+
+/// Sums every divisor of `number`, including 1 and the number itself.
+pub fn sum_of_divisors(number: usize) -> usize {
+    let mut sum = 0;
+    let mut divisor = 1;
+    // Dividing rather than squaring the divisor keeps this from overflowing near `usize::MAX`.
+    while divisor <= number / divisor {
+        if number.is_multiple_of(divisor) {
+            sum += divisor;
+            if divisor != number / divisor {
+                sum += number / divisor;
+            }
+        }
+        divisor += 1;
+    }
+    sum
+}
+
+impl Program {
+    pub fn instructions(&self) -> &[Instruction<Opcode>] {
+        &self.instructions
+    }
+
+    /// Executes the instruction at `ip` and moves `ip` on to the next one. Returns false without
+    /// doing anything if `ip` is outside the program, which halts it.
+    pub fn step(&self, ip: &mut usize, registers: &mut Registers) -> Result<bool> {
+        let instruction = match self.instructions.get(*ip) {
+            Some(instruction) => instruction,
+            None => return Ok(false),
+        };
+        registers[self.ip_register] = *ip;
+        instruction.execute(registers)?;
+        *ip = registers[self.ip_register].wrapping_add(1);
+        Ok(true)
+    }
+
+    pub fn run(&self, mut registers: Registers) -> Result<Registers> {
+        let mut ip = 0;
+        while self.step(&mut ip, &mut registers)? {}
+        Ok(registers)
+    }
+
+    /// Looks for the five instructions at the heart of the divisor loop:
+    ///
+    /// ```text
+    /// mulr outer inner product
+    /// eqrr product target product
+    /// addr product ip ip
+    /// addi ip 1 ip
+    /// addr outer 0 0
+    /// ```
+    pub fn find_divisor_loop(&self) -> Option<DivisorLoop> {
+        let ip = self.ip_register;
+        self.instructions
+            .windows(5)
+            .enumerate()
+            .find_map(|(start, window)| match window {
+                [Instruction {
+                    opcode: Opcode::Mulr,
+                    a: outer,
+                    b: inner,
+                    c: product,
+                }, Instruction {
+                    opcode: Opcode::Eqrr,
+                    a: compared_a,
+                    b: compared_b,
+                    c: found,
+                }, Instruction {
+                    opcode: Opcode::Addr,
+                    a: skip,
+                    b: skip_ip,
+                    c: skip_ip_out,
+                }, Instruction {
+                    opcode: Opcode::Addi,
+                    a: next_ip,
+                    b: 1,
+                    c: next_ip_out,
+                }, Instruction {
+                    opcode: Opcode::Addr,
+                    a: divisor,
+                    b: 0,
+                    c: 0,
+                }] if found == product
+                    && skip == product
+                    && [*skip_ip, *skip_ip_out, *next_ip, *next_ip_out] == [ip; 4]
+                    && (divisor == outer || divisor == inner) =>
+                {
+                    let target = if compared_a == product {
+                        *compared_b
+                    } else if compared_b == product {
+                        *compared_a
+                    } else {
+                        return None;
+                    };
+                    Some(DivisorLoop { start, target })
+                }
+                _ => None,
+            })
+    }
+
+    /// Runs the program's setup until it reaches the divisor loop, then works out what the loop
+    /// would leave in register 0 instead of running it, since it takes quadratic time in the
+    /// target number.
+    pub fn run_with_divisor_shortcut(&self, mut registers: Registers) -> Result<usize> {
+        let divisor_loop = self.find_divisor_loop().ok_or_else(|| {
+            Error::simulation(
+                "Program has no recognized divisor loop (mulr, eqrr, addr ip, addi ip 1, \
+                 addr into register 0) to shortcut, and is too slow to run directly",
+            )
+        })?;
+        let mut ip = 0;
+        let mut steps = 0;
+        while ip != divisor_loop.start {
+            if !self.step(&mut ip, &mut registers)? {
+                return Ok(registers[0]);
+            }
+            steps += 1;
+            if steps > SETUP_STEP_LIMIT {
+                return Err(Error::simulation(format!(
+                    "Program did not reach its divisor loop at instruction {} within {} steps",
+                    divisor_loop.start, SETUP_STEP_LIMIT
+                )));
+            }
+        }
+        Ok(registers[0] + sum_of_divisors(registers[divisor_loop.target]))
+    }
+}
+
+pub fn read_program(input: &Input) -> Result<Program> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    Ok(read_program(input)?.run([0; 6])?[0])
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    read_program(input)?.run_with_divisor_shortcut([1, 0, 0, 0, 0, 0])
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = Program;
+
+    fn parse(input: &Input) -> Result<Program> {
+        read_program(input)
+    }
+
+    fn part1(program: &Program) -> Result<Answer> {
+        Ok(program.run([0; 6])?[0].into())
+    }
+
+    fn part2(program: &Program) -> Result<Answer> {
+        Ok(program
+            .run_with_divisor_shortcut([1, 0, 0, 0, 0, 0])?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/19_test.txt";
+    const TEST_INPUT_PART_2: &str = "inputs/19_test_part2.txt";
+
+    #[test]
+    fn reads_program_file() {
+        let program = read_program(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(program.ip_register, 0);
+        assert_eq!(program.instructions.len(), 7);
+        assert_eq!(
+            program.instructions[3],
+            Instruction {
+                opcode: Opcode::Addr,
+                a: 1,
+                b: 2,
+                c: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn runs_program() {
+        let program = read_program(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(program.run([0; 6]).unwrap(), [6, 5, 6, 0, 0, 9]);
+    }
+
+    #[test]
+    fn sums_divisors() {
+        assert_eq!(sum_of_divisors(1), 1);
+        assert_eq!(sum_of_divisors(16), 31);
+        assert_eq!(sum_of_divisors(860), 1848);
+    }
+
+    #[test]
+    fn finds_divisor_loop() {
+        let program = read_program(&Input::file(TEST_INPUT_PART_2)).unwrap();
+        assert_eq!(
+            program.find_divisor_loop(),
+            Some(DivisorLoop {
+                start: 3,
+                target: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn shortcut_matches_running_the_loop() {
+        let program = read_program(&Input::file(TEST_INPUT_PART_2)).unwrap();
+        assert_eq!(program.run([0; 6]).unwrap()[0], 1848);
+        assert_eq!(program.run_with_divisor_shortcut([0; 6]).unwrap(), 1848);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(
+            solve_part2(&Input::file(TEST_INPUT_PART_2)).unwrap(),
+            22_157_688
+        );
+    }
+
+    #[test]
+    fn errors_without_divisor_loop() {
+        let program = read_program(&Input::file(TEST_INPUT)).unwrap();
+        assert!(program
+            .run_with_divisor_shortcut([1, 0, 0, 0, 0, 0])
+            .is_err());
+    }
+
+    #[test]
+    fn errors_on_unknown_opcode() {
+        match read_program(&Input::text("#ip 0\nseti 5 0 1\nmovi 6 0 2\n")) {
+            Ok(_) => panic!("read_program should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 3, column 1: Unknown opcode \"movi\": \"movi 6 0 2\""
+            ),
+        }
+        assert!(read_program(&Input::text("seti 5 0 1\n")).is_err());
+        assert!(read_program(&Input::text("#ip 6\nseti 5 0 1\n")).is_err());
+    }
+}
@@ -1,25 +1,30 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
 
-const INPUT: &str = "inputs/2.txt";
+use crate::error::Result;
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-pub fn solve_part1() -> Result<usize, Box<Error>> {
-    calculate_checksum(INPUT)
+pub const INPUT: &str = "inputs/2.txt";
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    calculate_checksum(input)
+}
+
+pub fn solve_part2(input: &Input) -> Result<Option<String>> {
+    find_most_common_id_overlap(input)
 }
 
-pub fn solve_part2() -> Result<Option<String>, Box<Error>> {
-    find_most_common_id_overlap(INPUT)
+pub fn calculate_checksum(input: &Input) -> Result<usize> {
+    Ok(checksum(&read_ids(input)?))
 }
 
-fn calculate_checksum(filename: &str) -> Result<usize, Box<Error>> {
+pub fn checksum(ids: &[String]) -> usize {
     let mut two_count = 0;
     let mut three_count = 0;
-    let file = File::open(filename)?;
-    for line in BufReader::new(file).lines() {
+    for id in ids {
         let mut char_map: HashMap<char, usize> = HashMap::new();
-        for c in line?.chars() {
+        for c in id.chars() {
             let current_count = char_map.get(&c).unwrap_or(&0);
             char_map.insert(c, current_count + 1);
         }
@@ -31,27 +36,279 @@ fn calculate_checksum(filename: &str) -> Result<usize, Box<Error>> {
             three_count += 1
         }
     }
-    Ok(two_count * three_count)
+    two_count * three_count
+}
+
+pub fn find_most_common_id_overlap(input: &Input) -> Result<Option<String>> {
+    Ok(most_common_id_overlap(&read_ids(input)?))
+}
+
+/// The common letters of the first pair of IDs that differ in at most one position, where pairs
+/// are ordered by the later ID in the list and then the earlier one.
+pub fn most_common_id_overlap(ids: &[String]) -> Option<String> {
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+    let mut first_identical = None;
+    for (index, id) in ids.iter().enumerate() {
+        match first_seen.get(id.as_str()) {
+            Some(&first) if first_identical.is_none() => first_identical = Some((index, first)),
+            Some(_) => {}
+            None => {
+                first_seen.insert(id, index);
+            }
+        }
+    }
+    let first_near_duplicate = find_near_duplicates(ids)
+        .into_iter()
+        .map(|pair| (pair.second, pair.first))
+        .min();
+    let (later, earlier) = match (first_identical, first_near_duplicate) {
+        (Some(identical), Some(near_duplicate)) => identical.min(near_duplicate),
+        (Some(pair), None) | (None, Some(pair)) => pair,
+        (None, None) => return None,
+    };
+    ids_are_diff_by_n(&ids[later], &ids[earlier], 1)
+}
+
+/// The original search, which compares every ID against all of the IDs before it. Kept to check
+/// the indexed search against.
+pub fn most_common_id_overlap_pairwise(ids: &[String]) -> Option<String> {
+    for (index, id) in ids.iter().enumerate() {
+        for id_before in ids[..index].iter() {
+            if let Some(common) = ids_are_diff_by_n(id, id_before, 1) {
+                return Some(common);
+            }
+        }
+    }
+    None
+}
+
+/// Two IDs, by their index in the list, that differ in exactly one position.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct NearDuplicate {
+    pub first: usize,
+    pub second: usize,
+    pub position: usize,
+}
+
+const HASH_BASE: u64 = 0x0000_0100_0000_01b3;
+
+/// Finds every pair of IDs that differ in exactly one position, sorted by the indices of the
+/// pair.
+///
+/// Two IDs of the same length differ only at position `p` if they are equal once the letter at
+/// `p` is masked out of both. So each distinct ID is hashed once per position with that position
+/// masked, and only IDs that end up with the same masked hash are compared. Hashes are checked
+/// against the letters themselves, so collisions can't produce false pairs.
+pub fn find_near_duplicates(ids: &[String]) -> Vec<NearDuplicate> {
+    // Identical IDs would share every masked hash, so they are only indexed once and expanded into
+    // pairs at the end.
+    let mut distinct: Vec<Vec<char>> = vec![];
+    let mut copies: Vec<Vec<usize>> = vec![];
+    let mut distinct_index: HashMap<&str, usize> = HashMap::new();
+    for (index, id) in ids.iter().enumerate() {
+        let next = distinct.len();
+        let entry = *distinct_index.entry(id).or_insert(next);
+        if entry == next {
+            distinct.push(id.chars().collect());
+            copies.push(vec![]);
+        }
+        copies[entry].push(index);
+    }
+
+    let mut masked_ids: Vec<(usize, usize, u64, usize)> = vec![];
+    for (index, chars) in distinct.iter().enumerate() {
+        let hash = chars.iter().fold(0u64, |hash, c| {
+            hash.wrapping_mul(HASH_BASE).wrapping_add(u64::from(*c))
+        });
+        let mut place = 1u64;
+        for position in (0..chars.len()).rev() {
+            let masked = hash.wrapping_sub(place.wrapping_mul(u64::from(chars[position])));
+            masked_ids.push((chars.len(), position, masked, index));
+            place = place.wrapping_mul(HASH_BASE);
+        }
+    }
+    // Sorting brings the IDs that share a length, masked position and hash next to each other.
+    masked_ids.sort_unstable();
+
+    let mut pairs = vec![];
+    for bucket in masked_ids.chunk_by(|a, b| a.0 == b.0 && a.1 == b.1 && a.2 == b.2) {
+        let position = bucket[0].1;
+        for (member_index, &(_, _, _, a)) in bucket.iter().enumerate() {
+            for &(_, _, _, b) in bucket[..member_index].iter() {
+                let differs_only_at_position = distinct[a]
+                    .iter()
+                    .zip(distinct[b].iter())
+                    .enumerate()
+                    .all(|(at, (x, y))| (at == position) != (x == y));
+                if !differs_only_at_position {
+                    continue;
+                }
+                for &first in copies[a].iter() {
+                    for &second in copies[b].iter() {
+                        pairs.push(NearDuplicate {
+                            first: first.min(second),
+                            second: first.max(second),
+                            position,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// How to measure how different two IDs are.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Metric {
+    /// The number of positions at which IDs of the same length differ. IDs of different lengths
+    /// are never similar.
+    Hamming,
+    /// The number of letters that have to be inserted, deleted or substituted to turn one ID
+    /// into the other.
+    Levenshtein,
+}
+
+/// Two IDs, by their index in the list, and the distance between them.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct SimilarPair {
+    pub first: usize,
+    pub second: usize,
+    pub distance: usize,
+}
+
+pub fn hamming_distance(first: &[char], second: &[char]) -> Option<usize> {
+    if first.len() != second.len() {
+        return None;
+    }
+    Some(first.iter().zip(second.iter()).filter(|(a, b)| a != b).count())
+}
+
+/// The Levenshtein distance between the two IDs, if it is at most `limit`. Gives up as soon as
+/// every way of lining up the prefixes so far costs more than that.
+pub fn levenshtein_distance_within(first: &[char], second: &[char], limit: usize) -> Option<usize> {
+    if first.len().abs_diff(second.len()) > limit {
+        return None;
+    }
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    let mut current = vec![0; second.len() + 1];
+    for (i, a) in first.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in second.iter().enumerate() {
+            let substitution = previous[j] + if a == b { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().all(|&distance| distance > limit) {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    Some(previous[second.len()]).filter(|&distance| distance <= limit)
 }
 
-fn find_most_common_id_overlap(filename: &str) -> Result<Option<String>, Box<Error>> {
-    let file = File::open(filename)?;
-    let mut read_lines: Vec<String> = Vec::new();
-    for line in BufReader::new(file).lines() {
-        let line_ref = &line?;
-        for line_before in read_lines.iter() {
-            match ids_are_diff_by_n(line_ref, &line_before, 1) {
-                Some(common) => return Ok(Some(common)),
-                None => (),
+/// Finds every pair of IDs at most `limit` apart by `metric`, sorted by the indices of the pair.
+/// This compares every pair of IDs whose lengths are close enough for them to be similar, so for
+/// pairs that differ by exactly one letter `find_near_duplicates` is much faster.
+pub fn find_similar_ids(ids: &[String], metric: Metric, limit: usize) -> Vec<SimilarPair> {
+    let ids: Vec<Vec<char>> = ids.iter().map(|id| id.chars().collect()).collect();
+    let mut by_length: Vec<usize> = (0..ids.len()).collect();
+    by_length.sort_by_key(|&index| ids[index].len());
+
+    let mut pairs = vec![];
+    for (sorted_index, &a) in by_length.iter().enumerate() {
+        for &b in by_length[sorted_index + 1..].iter() {
+            if ids[b].len() - ids[a].len() > limit {
+                break;
+            }
+            let distance = match metric {
+                Metric::Hamming => hamming_distance(&ids[a], &ids[b]).filter(|&d| d <= limit),
+                Metric::Levenshtein => levenshtein_distance_within(&ids[a], &ids[b], limit),
+            };
+            if let Some(distance) = distance {
+                pairs.push(SimilarPair {
+                    first: a.min(b),
+                    second: a.max(b),
+                    distance,
+                });
             }
         }
+    }
+    pairs.sort_unstable();
+    pairs
+}
 
-        read_lines.push(line_ref.to_owned());
+/// Groups IDs into clusters where every ID is similar to every other ID in the same cluster, by
+/// finding the maximal cliques of the similar pairs. A chain of similar pairs whose ends are far
+/// apart is split into overlapping clusters, so an ID can be in more than one. IDs that aren't
+/// similar to any other are left out. Each cluster lists its IDs' indices in order, and the
+/// clusters are sorted.
+pub fn cluster_similar_ids(id_count: usize, pairs: &[SimilarPair]) -> Vec<Vec<usize>> {
+    let mut similar: Vec<HashSet<usize>> = vec![HashSet::new(); id_count];
+    for pair in pairs {
+        if pair.first != pair.second {
+            similar[pair.first].insert(pair.second);
+            similar[pair.second].insert(pair.first);
+        }
     }
-    Ok(None)
+    let candidates: HashSet<usize> = (0..id_count)
+        .filter(|&index| !similar[index].is_empty())
+        .collect();
+    let mut clusters = vec![];
+    extend_clusters(&similar, &mut vec![], candidates, HashSet::new(), &mut clusters);
+    clusters.sort_unstable();
+    clusters
 }
 
-fn ids_are_diff_by_n(first: &String, second: &String, n: usize) -> Option<String> {
+/// Bron-Kerbosch with pivoting: records every maximal clique that contains all of `cluster`,
+/// some of `candidates` and none of `excluded`. Only candidates not similar to the pivot are
+/// branched on, since any clique through the others could add the pivot as well.
+fn extend_clusters(
+    similar: &[HashSet<usize>],
+    cluster: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    clusters: &mut Vec<Vec<usize>>,
+) {
+    let pivot = match candidates
+        .union(&excluded)
+        .max_by_key(|&&index| similar[index].intersection(&candidates).count())
+    {
+        Some(&pivot) => pivot,
+        None => {
+            if cluster.len() > 1 {
+                let mut found = cluster.clone();
+                found.sort_unstable();
+                clusters.push(found);
+            }
+            return;
+        }
+    };
+    let branches: Vec<usize> = candidates.difference(&similar[pivot]).cloned().collect();
+    for index in branches {
+        cluster.push(index);
+        extend_clusters(
+            similar,
+            cluster,
+            candidates.intersection(&similar[index]).cloned().collect(),
+            excluded.intersection(&similar[index]).cloned().collect(),
+            clusters,
+        );
+        cluster.pop();
+        candidates.remove(&index);
+        excluded.insert(index);
+    }
+}
+
+pub fn read_ids(input: &Input) -> Result<Vec<String>> {
+    let mut ids = Vec::new();
+    for line in input.reader()?.lines() {
+        ids.push(line?);
+    }
+    Ok(ids)
+}
+
+pub fn ids_are_diff_by_n(first: &str, second: &str, n: usize) -> Option<String> {
     if first.len() != second.len() {
         return None;
     }
@@ -79,30 +336,49 @@ fn ids_are_diff_by_n(first: &String, second: &String, n: usize) -> Option<String
     Some(common)
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        read_ids(input)
+    }
+
+    fn part1(ids: &Vec<String>) -> Result<Answer> {
+        Ok(checksum(ids).into())
+    }
+
+    fn part2(ids: &Vec<String>) -> Result<Answer> {
+        Ok(most_common_id_overlap(ids).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::Lcg;
 
     const TEST_INPUT_PART_1: &str = "inputs/2_test.txt";
     const TEST_INPUT_PART_2: &str = "inputs/2_test_part2.txt";
 
     #[test]
     fn calculates_correct_checksum() {
-        assert_eq!(calculate_checksum(TEST_INPUT_PART_1).unwrap(), 12);
+        assert_eq!(calculate_checksum(&Input::file(TEST_INPUT_PART_1)).unwrap(), 12);
     }
 
     #[test]
     fn ids_are_diff_by_1() {
         assert_eq!(
-            ids_are_diff_by_n(&String::from("abcdef"), &String::from("abbdef"), 1).unwrap(),
+            ids_are_diff_by_n("abcdef", "abbdef", 1).unwrap(),
             "abdef"
         );
         assert_eq!(
-            ids_are_diff_by_n(&String::from("abcdef"), &String::from("abcdee"), 1).unwrap(),
+            ids_are_diff_by_n("abcdef", "abcdee", 1).unwrap(),
             "abcde"
         );
         assert_eq!(
-            ids_are_diff_by_n(&String::from("abcdef"), &String::from("bbcdef"), 1).unwrap(),
+            ids_are_diff_by_n("abcdef", "bbcdef", 1).unwrap(),
             "bcdef"
         );
     }
@@ -110,7 +386,7 @@ mod tests {
     #[test]
     fn ids_are_diff_by_2() {
         assert_eq!(
-            ids_are_diff_by_n(&String::from("abcdef"), &String::from("abbdxf"), 2).unwrap(),
+            ids_are_diff_by_n("abcdef", "abbdxf", 2).unwrap(),
             "abdf"
         );
     }
@@ -118,7 +394,7 @@ mod tests {
     #[test]
     fn ids_are_diff_by_n_len_unequal_false() {
         assert_eq!(
-            ids_are_diff_by_n(&String::from("abcdef"), &String::from("abbdeff"), 1),
+            ids_are_diff_by_n("abcdef", "abbdeff", 1),
             None
         );
     }
@@ -126,15 +402,209 @@ mod tests {
     #[test]
     fn ids_are_diff_by_1_too_many_diffs() {
         assert_eq!(
-            ids_are_diff_by_n(&String::from("abcdef"), &String::from("abbdxf"), 1),
+            ids_are_diff_by_n("abcdef", "abbdxf", 1),
+            None
+        );
+    }
+
+    fn generate_ids(count: usize, length: usize, letters: u32) -> Vec<String> {
+        let mut lcg = Lcg::new(7);
+        (0..count)
+            .map(|_| {
+                (0..length)
+                    .map(|_| std::char::from_u32('a' as u32 + lcg.below(letters)).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_near_duplicates() {
+        let mut ids = read_ids(&Input::file(TEST_INPUT_PART_2)).unwrap();
+        ids.push("fghij".to_string());
+        assert_eq!(
+            find_near_duplicates(&ids),
+            vec![
+                NearDuplicate {
+                    first: 1,
+                    second: 4,
+                    position: 2,
+                },
+                NearDuplicate {
+                    first: 4,
+                    second: 7,
+                    position: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn near_duplicates_match_comparing_every_pair() {
+        // Few letters and short IDs, so that plenty of pairs differ by one letter, and some IDs
+        // repeat or differ in length.
+        let mut ids = generate_ids(400, 4, 3);
+        ids.extend(generate_ids(100, 3, 3));
+        let mut expected = vec![];
+        for second in 0..ids.len() {
+            for first in 0..second {
+                if ids[first] != ids[second]
+                    && ids_are_diff_by_n(&ids[first], &ids[second], 1).is_some()
+                {
+                    let position = ids[first]
+                        .chars()
+                        .zip(ids[second].chars())
+                        .position(|(a, b)| a != b)
+                        .unwrap();
+                    expected.push(NearDuplicate {
+                        first,
+                        second,
+                        position,
+                    });
+                }
+            }
+        }
+        expected.sort_unstable();
+        assert_eq!(find_near_duplicates(&ids), expected);
+    }
+
+    #[test]
+    fn overlap_matches_pairwise_search() {
+        let sizes = [(50, 5, 4), (200, 6, 3), (300, 8, 26), (20, 3, 2)];
+        for &(count, length, letters) in sizes.iter() {
+            let ids = generate_ids(count, length, letters);
+            assert_eq!(
+                most_common_id_overlap(&ids),
+                most_common_id_overlap_pairwise(&ids)
+            );
+        }
+        let ids: Vec<String> = ["abc", "xyz", "abc", "xyy"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        assert_eq!(most_common_id_overlap(&ids), Some("abc".to_string()));
+        assert_eq!(most_common_id_overlap_pairwise(&ids), Some("abc".to_string()));
+    }
+
+    #[test]
+    fn finds_overlap_among_many_ids() {
+        let mut ids = generate_ids(20_000, 26, 26);
+        let mut near_duplicate: Vec<char> = ids[1234].chars().collect();
+        near_duplicate[10] = if near_duplicate[10] == 'a' { 'b' } else { 'a' };
+        ids.push(near_duplicate.into_iter().collect());
+        let mut common: String = ids[1234].clone();
+        common.remove(10);
+        assert_eq!(most_common_id_overlap(&ids), Some(common));
+    }
+
+    fn chars(id: &str) -> Vec<char> {
+        id.chars().collect()
+    }
+
+    #[test]
+    fn measures_distances() {
+        assert_eq!(hamming_distance(&chars("abcdef"), &chars("abbdxf")), Some(2));
+        assert_eq!(hamming_distance(&chars("abcdef"), &chars("abcdefg")), None);
+        assert_eq!(
+            levenshtein_distance_within(&chars("kitten"), &chars("sitting"), 3),
+            Some(3)
+        );
+        assert_eq!(
+            levenshtein_distance_within(&chars("kitten"), &chars("sitting"), 2),
             None
         );
+        assert_eq!(
+            levenshtein_distance_within(&chars("abcdef"), &chars("bcdefg"), 2),
+            Some(2)
+        );
+        assert_eq!(
+            levenshtein_distance_within(&chars(""), &chars("abc"), 3),
+            Some(3)
+        );
+        assert_eq!(
+            levenshtein_distance_within(&chars("abc"), &chars("abc"), 0),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn finds_similar_ids() {
+        let ids: Vec<String> = ["abcde", "fghij", "abde", "fguij", "xbcdy", "fghij"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let pair = |first, second, distance| SimilarPair {
+            first,
+            second,
+            distance,
+        };
+        assert_eq!(
+            find_similar_ids(&ids, Metric::Hamming, 1),
+            vec![pair(1, 3, 1), pair(1, 5, 0), pair(3, 5, 1)]
+        );
+        assert_eq!(
+            find_similar_ids(&ids, Metric::Levenshtein, 2),
+            vec![
+                pair(0, 2, 1),
+                pair(0, 4, 2),
+                pair(1, 3, 1),
+                pair(1, 5, 0),
+                pair(3, 5, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn similar_ids_match_near_duplicates() {
+        let mut ids = generate_ids(300, 4, 3);
+        ids.extend(generate_ids(50, 3, 3));
+        let near_duplicates: Vec<(usize, usize)> = find_near_duplicates(&ids)
+            .into_iter()
+            .map(|pair| (pair.first, pair.second))
+            .collect();
+        let similar: Vec<(usize, usize)> = find_similar_ids(&ids, Metric::Hamming, 1)
+            .into_iter()
+            .filter(|pair| pair.distance == 1)
+            .map(|pair| (pair.first, pair.second))
+            .collect();
+        assert_eq!(similar, near_duplicates);
+    }
+
+    #[test]
+    fn clusters_similar_ids() {
+        let ids: Vec<String> = ["abcde", "fghij", "abde", "fguij", "xbcdy", "fghij", "zzzzz"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let pairs = find_similar_ids(&ids, Metric::Levenshtein, 1);
+        assert_eq!(
+            cluster_similar_ids(ids.len(), &pairs),
+            vec![vec![0, 2], vec![1, 3, 5]]
+        );
+        // abde and xbcdy are both within 2 of abcde, but 3 apart from each other.
+        let pairs = find_similar_ids(&ids, Metric::Levenshtein, 2);
+        assert_eq!(
+            cluster_similar_ids(ids.len(), &pairs),
+            vec![vec![0, 2], vec![0, 4], vec![1, 3, 5]]
+        );
+    }
+
+    #[test]
+    fn splits_chains_of_similar_ids() {
+        let ids: Vec<String> = ["abc", "abd", "aed", "xyz"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let pairs = find_similar_ids(&ids, Metric::Hamming, 1);
+        assert_eq!(cluster_similar_ids(ids.len(), &pairs), vec![vec![0, 1], vec![1, 2]]);
+        let pairs = find_similar_ids(&ids, Metric::Hamming, 2);
+        assert_eq!(cluster_similar_ids(ids.len(), &pairs), vec![vec![0, 1, 2]]);
     }
 
     #[test]
     fn finds_most_common_id_overlap() {
         assert_eq!(
-            find_most_common_id_overlap(TEST_INPUT_PART_2).unwrap().unwrap(),
+            find_most_common_id_overlap(&Input::file(TEST_INPUT_PART_2)).unwrap().unwrap(),
             "fgij"
         );
     }
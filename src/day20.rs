@@ -0,0 +1,279 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/20.txt";
+const FAR_ROOM_DOORS: usize = 1000;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Position {
+    pub y: i32,
+    pub x: i32,
+}
+
+const ORIGIN: Position = Position { x: 0, y: 0 };
+
+/// The rooms of the facility and the doors between them, keyed by room.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RoomMap {
+    doors: HashMap<Position, HashSet<Position>>,
+}
+
+impl Position {
+    fn step(self, direction: char) -> Position {
+        let Position { x, y } = self;
+        match direction {
+            'N' => Position { x, y: y - 1 },
+            'S' => Position { x, y: y + 1 },
+            'E' => Position { x: x + 1, y },
+            _ => Position { x: x - 1, y },
+        }
+    }
+}
+
+/// Follows every route the regex describes. Rather than expanding each route, this keeps the
+/// set of rooms the routes so far could have reached, so that branches which end up in the same
+/// room don't multiply.
+impl FromStr for RoomMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<RoomMap> {
+        let regex = s.trim_end();
+        let error =
+            |column: usize, message: &str| Error::parse(1, regex, message).at_column(column);
+        if !regex.starts_with('^') {
+            return Err(error(1, "Route regex must start with ^"));
+        }
+
+        let mut map = RoomMap {
+            doors: HashMap::new(),
+        };
+        map.doors.insert(ORIGIN, HashSet::new());
+        let mut current: HashSet<Position> = [ORIGIN].iter().cloned().collect();
+        // For each open group, the rooms it started from and the rooms its finished branches
+        // ended in.
+        let mut groups: Vec<(HashSet<Position>, HashSet<Position>)> = vec![];
+        let mut ended = false;
+        for (index, c) in regex.char_indices().skip(1) {
+            if ended {
+                return Err(error(index + 1, "Unexpected characters after $"));
+            }
+            match c {
+                'N' | 'E' | 'S' | 'W' => {
+                    current = current
+                        .into_iter()
+                        .map(|room| {
+                            let next_room = room.step(c);
+                            map.add_door(room, next_room);
+                            next_room
+                        })
+                        .collect();
+                }
+                '(' => groups.push((current.clone(), HashSet::new())),
+                '|' => match groups.last_mut() {
+                    Some((starts, ends)) => {
+                        ends.extend(current.drain());
+                        current = starts.clone();
+                    }
+                    None => return Err(error(index + 1, "Branch | outside of a group")),
+                },
+                ')' => match groups.pop() {
+                    Some((_, ends)) => current.extend(ends),
+                    None => return Err(error(index + 1, "Unmatched )")),
+                },
+                '$' => ended = true,
+                _ => {
+                    return Err(error(
+                        index + 1,
+                        &format!("Unexpected character in route regex: {:?}", c),
+                    ))
+                }
+            }
+        }
+        if !groups.is_empty() {
+            return Err(error(regex.len(), "Unclosed ( in route regex"));
+        }
+        if !ended {
+            return Err(error(regex.len(), "Route regex must end with $"));
+        }
+        Ok(map)
+    }
+}
+
+impl fmt::Display for RoomMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let min_x = self.doors.keys().map(|room| room.x).min().unwrap_or(0);
+        let max_x = self.doors.keys().map(|room| room.x).max().unwrap_or(0);
+        let min_y = self.doors.keys().map(|room| room.y).min().unwrap_or(0);
+        let max_y = self.doors.keys().map(|room| room.y).max().unwrap_or(0);
+        let has_door = |room: Position, direction: char| {
+            self.doors
+                .get(&room)
+                .is_some_and(|doors| doors.contains(&room.step(direction)))
+        };
+
+        writeln!(f, "{}", "#".repeat((max_x - min_x + 1) as usize * 2 + 1))?;
+        for y in min_y..=max_y {
+            write!(f, "#")?;
+            for x in min_x..=max_x {
+                let room = Position { x, y };
+                if room == ORIGIN {
+                    write!(f, "X")?;
+                } else if self.doors.contains_key(&room) {
+                    write!(f, ".")?;
+                } else {
+                    write!(f, "#")?;
+                }
+                write!(f, "{}", if has_door(room, 'E') { '|' } else { '#' })?;
+            }
+            write!(f, "\n#")?;
+            for x in min_x..=max_x {
+                let room = Position { x, y };
+                write!(f, "{}#", if has_door(room, 'S') { '-' } else { '#' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl RoomMap {
+    fn add_door(&mut self, from: Position, to: Position) {
+        self.doors.entry(from).or_default().insert(to);
+        self.doors.entry(to).or_default().insert(from);
+    }
+
+    /// The fewest doors to pass through to reach each room from the starting room.
+    pub fn door_counts(&self) -> HashMap<Position, usize> {
+        let mut counts = HashMap::new();
+        let mut queue = VecDeque::new();
+        counts.insert(ORIGIN, 0);
+        queue.push_back(ORIGIN);
+        while let Some(room) = queue.pop_front() {
+            let count = counts[&room];
+            for next_room in self.doors[&room].iter() {
+                if !counts.contains_key(next_room) {
+                    counts.insert(*next_room, count + 1);
+                    queue.push_back(*next_room);
+                }
+            }
+        }
+        counts
+    }
+
+    pub fn furthest_room_doors(&self) -> usize {
+        self.door_counts().values().cloned().max().unwrap_or(0)
+    }
+
+    pub fn count_rooms_at_least(&self, doors: usize) -> usize {
+        self.door_counts()
+            .values()
+            .filter(|count| **count >= doors)
+            .count()
+    }
+}
+
+pub fn read_room_map(input: &Input) -> Result<RoomMap> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    Ok(read_room_map(input)?.furthest_room_doors())
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    Ok(read_room_map(input)?.count_rooms_at_least(FAR_ROOM_DOORS))
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = RoomMap;
+
+    fn parse(input: &Input) -> Result<RoomMap> {
+        read_room_map(input)
+    }
+
+    fn part1(map: &RoomMap) -> Result<Answer> {
+        Ok(map.furthest_room_doors().into())
+    }
+
+    fn part2(map: &RoomMap) -> Result<Answer> {
+        Ok(map.count_rooms_at_least(FAR_ROOM_DOORS).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/20_test.txt";
+
+    fn furthest_room_doors(regex: &str) -> usize {
+        regex.parse::<RoomMap>().unwrap().furthest_room_doors()
+    }
+
+    #[test]
+    fn renders_room_map() {
+        let map = read_room_map(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(
+            map.to_string(),
+            "#########\n\
+             #.|.|.|.#\n\
+             #-#######\n\
+             #.|.|.|.#\n\
+             #-#####-#\n\
+             #.#.#X|.#\n\
+             #-#-#####\n\
+             #.|.|.|.#\n\
+             #########\n"
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 10);
+        assert_eq!(furthest_room_doors("^WNE$"), 3);
+        assert_eq!(
+            furthest_room_doors("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$"),
+            18
+        );
+        assert_eq!(
+            furthest_room_doors("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$"),
+            23
+        );
+        assert_eq!(
+            furthest_room_doors(
+                "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$"
+            ),
+            31
+        );
+    }
+
+    #[test]
+    fn counts_far_rooms() {
+        let map = read_room_map(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(map.count_rooms_at_least(10), 1);
+        assert_eq!(map.count_rooms_at_least(0), 16);
+    }
+
+    #[test]
+    fn errors_on_malformed_regex() {
+        match read_room_map(&Input::text("^EN(W|S$\n")) {
+            Ok(_) => panic!("read_room_map should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 1, column 8: Unclosed ( in route regex: \"^EN(W|S$\""
+            ),
+        }
+        assert!("EN$".parse::<RoomMap>().is_err());
+        assert!("^EN)$".parse::<RoomMap>().is_err());
+        assert!("^EX$".parse::<RoomMap>().is_err());
+        assert!("^EN".parse::<RoomMap>().is_err());
+    }
+}
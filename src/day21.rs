@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+
+use crate::day16::{Instruction, Opcode};
+use crate::day19::{read_program, Program};
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/21.txt";
+
+/// Where the program checks register 0 to decide whether to halt.
+#[derive(Debug, PartialEq)]
+pub struct HaltCheck {
+    pub at: usize,
+    /// The register holding the value that register 0 is compared against.
+    pub register: usize,
+}
+
+/// The only instruction that reads register 0 is the equality check that halts the program, so
+/// that's the first `eqrr` that takes register 0 as one of its operands.
+pub fn find_halt_check(program: &Program) -> Result<HaltCheck> {
+    program
+        .instructions()
+        .iter()
+        .enumerate()
+        .find_map(|(at, instruction)| match instruction {
+            Instruction {
+                opcode: Opcode::Eqrr,
+                a: 0,
+                b: register,
+                ..
+            }
+            | Instruction {
+                opcode: Opcode::Eqrr,
+                a: register,
+                b: 0,
+                ..
+            } if *register != 0 => Some(HaltCheck {
+                at,
+                register: *register,
+            }),
+            _ => None,
+        })
+        .ok_or_else(|| Error::simulation("Program never compares a register against register 0"))
+}
+
+/// Runs the program with register 0 left at 0 and hands `observe` each value it compares register
+/// 0 against, in order, until `observe` returns false or the program halts. Each of those values
+/// is one that register 0 could be set to for the program to halt at that point.
+pub fn observe_halting_values<F>(program: &Program, mut observe: F) -> Result<()>
+where
+    F: FnMut(usize) -> bool,
+{
+    let check = find_halt_check(program)?;
+    let mut ip = 0;
+    let mut registers = [0; 6];
+    loop {
+        if ip == check.at && !observe(registers[check.register]) {
+            return Ok(());
+        }
+        if !program.step(&mut ip, &mut registers)? {
+            return Ok(());
+        }
+    }
+}
+
+/// The value for register 0 that halts the program after the fewest instructions: the first one
+/// it is compared against.
+pub fn fewest_instructions_value(program: &Program) -> Result<usize> {
+    let mut first = None;
+    observe_halting_values(program, |value| {
+        first = Some(value);
+        false
+    })?;
+    first.ok_or_else(|| Error::simulation("Program halted without checking register 0"))
+}
+
+/// The value for register 0 that halts the program after the most instructions. The values
+/// compared against come from a generator that eventually repeats itself, so the last new value
+/// before the first repeat is the last chance the program has to halt.
+pub fn most_instructions_value(program: &Program) -> Result<usize> {
+    let mut seen = HashSet::new();
+    let mut last = None;
+    observe_halting_values(program, |value| {
+        if !seen.insert(value) {
+            return false;
+        }
+        last = Some(value);
+        true
+    })?;
+    last.ok_or_else(|| Error::simulation("Program halted without checking register 0"))
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    fewest_instructions_value(&read_program(input)?)
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    most_instructions_value(&read_program(input)?)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed = Program;
+
+    fn parse(input: &Input) -> Result<Program> {
+        read_program(input)
+    }
+
+    fn part1(program: &Program) -> Result<Answer> {
+        Ok(fewest_instructions_value(program)?.into())
+    }
+
+    fn part2(program: &Program) -> Result<Answer> {
+        Ok(most_instructions_value(program)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/21_test.txt";
+
+    fn count_instructions(program: &Program, register_0: usize) -> usize {
+        let mut ip = 0;
+        let mut registers = [register_0, 0, 0, 0, 0, 0];
+        let mut count = 0;
+        while program.step(&mut ip, &mut registers).unwrap() {
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn finds_halt_check() {
+        let program = read_program(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(
+            find_halt_check(&program).unwrap(),
+            HaltCheck { at: 5, register: 3 }
+        );
+    }
+
+    #[test]
+    fn observes_halting_values_until_they_repeat() {
+        let program = read_program(&Input::file(TEST_INPUT)).unwrap();
+        let mut values = vec![];
+        observe_halting_values(&program, |value| {
+            values.push(value);
+            values.len() < 17
+        })
+        .unwrap();
+        assert_eq!(
+            values,
+            vec![22, 17, 24, 27, 26, 21, 28, 31, 30, 25, 16, 19, 18, 29, 20, 23, 22]
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 22);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT)).unwrap(), 23);
+    }
+
+    #[test]
+    fn halting_values_bound_the_instruction_counts() {
+        let program = read_program(&Input::file(TEST_INPUT)).unwrap();
+        let counts: Vec<usize> = (16..32)
+            .map(|value| count_instructions(&program, value))
+            .collect();
+        assert_eq!(
+            count_instructions(&program, 22),
+            *counts.iter().min().unwrap()
+        );
+        assert_eq!(
+            count_instructions(&program, 23),
+            *counts.iter().max().unwrap()
+        );
+    }
+
+    #[test]
+    fn errors_without_halt_check() {
+        let program = read_program(&Input::file("inputs/19_test.txt")).unwrap();
+        assert!(solve_part1(&Input::file("inputs/19_test.txt")).is_err());
+        assert!(find_halt_check(&program).is_err());
+    }
+}
@@ -0,0 +1,400 @@
+extern crate regex;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/22.txt";
+const EROSION_MODULO: usize = 20183;
+const X_AXIS_FACTOR: usize = 16807;
+const Y_AXIS_FACTOR: usize = 48271;
+const MOVE_MINUTES: usize = 1;
+const SWITCH_MINUTES: usize = 7;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Position {
+    pub y: usize,
+    pub x: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Region {
+    Rocky,
+    Wet,
+    Narrow,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Tool {
+    Torch,
+    ClimbingGear,
+    Neither,
+}
+
+/// The cave system's depth and where the target is, as given by the scan.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Scan {
+    pub depth: usize,
+    pub target: Position,
+}
+
+/// The erosion levels of the cave, worked out only as far as anything has asked about. Each
+/// level depends on the ones above and to the left of it, so the grid grows a row or column at a
+/// time instead of recursing.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cave {
+    scan: Scan,
+    erosion_levels: Vec<Vec<usize>>,
+}
+
+impl Position {
+    fn neighbors(self) -> Vec<Position> {
+        let Position { x, y } = self;
+        let mut neighbors = Vec::with_capacity(4);
+        if y > 0 {
+            neighbors.push(Position { x, y: y - 1 });
+        }
+        if x > 0 {
+            neighbors.push(Position { x: x - 1, y });
+        }
+        neighbors.push(Position { x: x + 1, y });
+        neighbors.push(Position { x, y: y + 1 });
+        neighbors
+    }
+}
+
+impl Region {
+    fn from_erosion_level(erosion_level: usize) -> Region {
+        match erosion_level % 3 {
+            0 => Region::Rocky,
+            1 => Region::Wet,
+            _ => Region::Narrow,
+        }
+    }
+
+    fn risk_level(self) -> usize {
+        match self {
+            Region::Rocky => 0,
+            Region::Wet => 1,
+            Region::Narrow => 2,
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Region::Rocky => '.',
+            Region::Wet => '=',
+            Region::Narrow => '|',
+        }
+    }
+
+    /// The two tools that can be equipped in this type of region.
+    fn tools(self) -> [Tool; 2] {
+        match self {
+            Region::Rocky => [Tool::ClimbingGear, Tool::Torch],
+            Region::Wet => [Tool::ClimbingGear, Tool::Neither],
+            Region::Narrow => [Tool::Torch, Tool::Neither],
+        }
+    }
+
+    fn allows(self, tool: Tool) -> bool {
+        self.tools().contains(&tool)
+    }
+}
+
+impl FromStr for Scan {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Scan> {
+        lazy_static! {
+            static ref DEPTH_REGEX: Regex = Regex::new(r"^depth: (?P<depth>\d+)$").unwrap();
+            static ref TARGET_REGEX: Regex =
+                Regex::new(r"^target: (?P<x>\d+),(?P<y>\d+)$").unwrap();
+        }
+        let mut lines = s.lines();
+        let depth_line = lines.next().unwrap_or("");
+        let depth = match DEPTH_REGEX.captures(depth_line) {
+            Some(captures) => parse_field(&captures, "depth", depth_line)?,
+            None => {
+                return Err(Error::parse(
+                    1,
+                    depth_line,
+                    "Malformed depth line, expected depth: <depth>",
+                ))
+            }
+        };
+        let target_line = lines.next().unwrap_or("");
+        let target = match TARGET_REGEX.captures(target_line) {
+            Some(captures) => Position {
+                x: parse_field(&captures, "x", target_line).map_err(|err| err.at_line(2))?,
+                y: parse_field(&captures, "y", target_line).map_err(|err| err.at_line(2))?,
+            },
+            None => {
+                return Err(Error::parse(
+                    2,
+                    target_line,
+                    "Malformed target line, expected target: <x>,<y>",
+                ))
+            }
+        };
+        Ok(Scan { depth, target })
+    }
+}
+
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let target = self.scan.target;
+        for y in 0..=target.y.min(self.erosion_levels.len().saturating_sub(1)) {
+            for (x, erosion_level) in self.erosion_levels[y].iter().enumerate().take(target.x + 1) {
+                let position = Position { x, y };
+                if position == (Position { x: 0, y: 0 }) {
+                    write!(f, "M")?;
+                } else if position == target {
+                    write!(f, "T")?;
+                } else {
+                    write!(f, "{}", Region::from_erosion_level(*erosion_level).symbol())?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Cave {
+    pub fn new(scan: Scan) -> Cave {
+        Cave {
+            scan,
+            erosion_levels: vec![],
+        }
+    }
+
+    /// Works out the erosion levels of every region up to and including `position`.
+    fn extend_to(&mut self, position: Position) {
+        let width = self
+            .erosion_levels
+            .first()
+            .map_or(0, |row| row.len())
+            .max(position.x + 1);
+        let height = self.erosion_levels.len().max(position.y + 1);
+        for y in 0..height {
+            if y == self.erosion_levels.len() {
+                self.erosion_levels.push(Vec::with_capacity(width));
+            }
+            for x in self.erosion_levels[y].len()..width {
+                let geologic_index = match (x, y) {
+                    (0, 0) => 0,
+                    _ if (Position { x, y }) == self.scan.target => 0,
+                    (x, 0) => x * X_AXIS_FACTOR,
+                    (0, y) => y * Y_AXIS_FACTOR,
+                    (x, y) => self.erosion_levels[y][x - 1] * self.erosion_levels[y - 1][x],
+                };
+                self.erosion_levels[y].push((geologic_index + self.scan.depth) % EROSION_MODULO);
+            }
+        }
+    }
+
+    pub fn erosion_level(&mut self, position: Position) -> usize {
+        if self
+            .erosion_levels
+            .get(position.y)
+            .is_none_or(|row| position.x >= row.len())
+        {
+            self.extend_to(position);
+        }
+        self.erosion_levels[position.y][position.x]
+    }
+
+    pub fn region(&mut self, position: Position) -> Region {
+        Region::from_erosion_level(self.erosion_level(position))
+    }
+
+    /// Sums the risk level of every region in the rectangle from the mouth to the target.
+    pub fn risk_level(&mut self) -> usize {
+        let target = self.scan.target;
+        self.extend_to(target);
+        (0..=target.y)
+            .flat_map(|y| (0..=target.x).map(move |x| Position { x, y }))
+            .map(|position| self.region(position).risk_level())
+            .sum()
+    }
+
+    /// Searches for the quickest way from the mouth to the target with the torch equipped, over
+    /// every combination of region and equipped tool. The cave goes on past the target, and the
+    /// quickest way there can run through regions beyond it, so those are worked out as the
+    /// search reaches them.
+    ///
+    /// The target's region depends only on the depth, and if it's wet the torch can never be
+    /// equipped there, so that's an error rather than an endless search.
+    pub fn fewest_minutes_to_target(&mut self) -> Result<usize> {
+        let start = (Position { x: 0, y: 0 }, Tool::Torch);
+        let goal = (self.scan.target, Tool::Torch);
+        if !self.region(goal.0).allows(goal.1) {
+            return Err(Error::simulation(
+                "The target is in a wet region, where the torch can't be equipped",
+            ));
+        }
+        let mut minutes: HashMap<(Position, Tool), usize> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        minutes.insert(start, 0);
+        queue.push(Reverse((0, start.0, start.1)));
+        while let Some(Reverse((elapsed, position, tool))) = queue.pop() {
+            if (position, tool) == goal {
+                return Ok(elapsed);
+            }
+            if minutes
+                .get(&(position, tool))
+                .is_some_and(|&best| best < elapsed)
+            {
+                continue;
+            }
+
+            let region = self.region(position);
+            let mut moves = vec![];
+            for other_tool in region.tools().iter() {
+                if *other_tool != tool {
+                    moves.push((position, *other_tool, SWITCH_MINUTES));
+                }
+            }
+            for next_position in position.neighbors() {
+                if self.region(next_position).allows(tool) {
+                    moves.push((next_position, tool, MOVE_MINUTES));
+                }
+            }
+            for (next_position, next_tool, cost) in moves {
+                let next_elapsed = elapsed + cost;
+                let best = minutes
+                    .entry((next_position, next_tool))
+                    .or_insert(usize::MAX);
+                if next_elapsed < *best {
+                    *best = next_elapsed;
+                    queue.push(Reverse((next_elapsed, next_position, next_tool)));
+                }
+            }
+        }
+        unreachable!(
+            "Neighboring regions always share a tool and the cave goes on forever, so the target \
+             can always be reached"
+        )
+    }
+}
+
+pub fn read_scan(input: &Input) -> Result<Scan> {
+    let input = input.read_to_string()?;
+    input.parse()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    Ok(Cave::new(read_scan(input)?).risk_level())
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    Cave::new(read_scan(input)?).fewest_minutes_to_target()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Scan;
+
+    fn parse(input: &Input) -> Result<Scan> {
+        read_scan(input)
+    }
+
+    fn part1(scan: &Scan) -> Result<Answer> {
+        Ok(Cave::new(*scan).risk_level().into())
+    }
+
+    fn part2(scan: &Scan) -> Result<Answer> {
+        Ok(Cave::new(*scan).fewest_minutes_to_target()?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/22_test.txt";
+
+    #[test]
+    fn reads_scan_file() {
+        assert_eq!(
+            read_scan(&Input::file(TEST_INPUT)).unwrap(),
+            Scan {
+                depth: 510,
+                target: Position { x: 10, y: 10 },
+            }
+        );
+    }
+
+    #[test]
+    fn calculates_erosion_levels() {
+        let mut cave = Cave::new(read_scan(&Input::file(TEST_INPUT)).unwrap());
+        assert_eq!(cave.erosion_level(Position { x: 0, y: 0 }), 510);
+        assert_eq!(cave.erosion_level(Position { x: 1, y: 0 }), 17317);
+        assert_eq!(cave.erosion_level(Position { x: 0, y: 1 }), 8415);
+        assert_eq!(cave.erosion_level(Position { x: 1, y: 1 }), 1805);
+        assert_eq!(cave.erosion_level(Position { x: 10, y: 10 }), 510);
+        assert_eq!(cave.region(Position { x: 1, y: 1 }), Region::Narrow);
+    }
+
+    #[test]
+    fn renders_cave() {
+        let mut cave = Cave::new(read_scan(&Input::file(TEST_INPUT)).unwrap());
+        cave.risk_level();
+        assert_eq!(
+            cave.to_string(),
+            "M=.|=.|.|=.\n\
+             .|=|=|||..|\n\
+             .==|....||=\n\
+             =.|....|.==\n\
+             =|..==...=.\n\
+             =||.=.=||=|\n\
+             |.=.===|||.\n\
+             |..==||=.|=\n\
+             .=..===..=|\n\
+             .======|||=\n\
+             .===|=|===T\n"
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 114);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT)).unwrap(), 45);
+    }
+
+    #[test]
+    fn errors_on_malformed_scan() {
+        match read_scan(&Input::text("depth: 510\ntarget: 10;10\n")) {
+            Ok(_) => panic!("read_scan should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Malformed target line, expected target: <x>,<y>: \"target: 10;10\""
+            ),
+        }
+        assert!(read_scan(&Input::text("depth 510\ntarget: 10,10\n")).is_err());
+    }
+
+    #[test]
+    fn errors_when_target_is_wet() {
+        match solve_part2(&Input::text("depth: 511\ntarget: 10,10\n")) {
+            Ok(_) => panic!("solve_part2 should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "The target is in a wet region, where the torch can't be equipped"
+            ),
+        }
+    }
+}
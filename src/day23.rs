@@ -0,0 +1,343 @@
+extern crate regex;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/23.txt";
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Nanobot {
+    pub position: Position,
+    pub radius: i64,
+}
+
+/// A cube of positions, `size` long on each side, starting at `min` and going in the positive
+/// direction along each axis.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Cube {
+    min: Position,
+    size: i64,
+}
+
+impl Position {
+    const ORIGIN: Position = Position { x: 0, y: 0, z: 0 };
+
+    pub fn distance(self, other: Position) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl FromStr for Nanobot {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Nanobot> {
+        lazy_static! {
+            static ref NANOBOT_REGEX: Regex =
+                Regex::new(r"^pos=<(?P<x>-?\d+),(?P<y>-?\d+),(?P<z>-?\d+)>, r=(?P<radius>\d+)$")
+                    .unwrap();
+        }
+        let captures = match NANOBOT_REGEX.captures(s) {
+            Some(captures) => captures,
+            None => {
+                return Err(Error::parse(
+                    1,
+                    s,
+                    "Malformed nanobot line, expected pos=<x,y,z>, r=<radius>",
+                ))
+            }
+        };
+        Ok(Nanobot {
+            position: Position {
+                x: parse_field(&captures, "x", s)?,
+                y: parse_field(&captures, "y", s)?,
+                z: parse_field(&captures, "z", s)?,
+            },
+            radius: parse_field(&captures, "radius", s)?,
+        })
+    }
+}
+
+impl Nanobot {
+    pub fn in_range(&self, position: Position) -> bool {
+        self.position.distance(position) <= self.radius
+    }
+
+    fn reaches(&self, cube: &Cube) -> bool {
+        cube.distance(self.position) <= self.radius
+    }
+}
+
+impl Cube {
+    fn max(&self) -> Position {
+        Position {
+            x: self.min.x + self.size - 1,
+            y: self.min.y + self.size - 1,
+            z: self.min.z + self.size - 1,
+        }
+    }
+
+    /// The distance from `position` to the closest position in the cube.
+    fn distance(&self, position: Position) -> i64 {
+        let max = self.max();
+        let axis = |at: i64, min: i64, max: i64| {
+            if at < min {
+                min - at
+            } else if at > max {
+                at - max
+            } else {
+                0
+            }
+        };
+        axis(position.x, self.min.x, max.x)
+            + axis(position.y, self.min.y, max.y)
+            + axis(position.z, self.min.z, max.z)
+    }
+
+    /// Splits the cube into its eight octants.
+    fn split(&self) -> Vec<Cube> {
+        let size = self.size / 2;
+        let mut octants = Vec::with_capacity(8);
+        for &x in [self.min.x, self.min.x + size].iter() {
+            for &y in [self.min.y, self.min.y + size].iter() {
+                for &z in [self.min.z, self.min.z + size].iter() {
+                    octants.push(Cube {
+                        min: Position { x, y, z },
+                        size,
+                    });
+                }
+            }
+        }
+        octants
+    }
+}
+
+pub fn strongest(nanobots: &[Nanobot]) -> Result<&Nanobot> {
+    nanobots
+        .iter()
+        .max_by_key(|nanobot| nanobot.radius)
+        .ok_or_else(|| Error::simulation("There are no nanobots"))
+}
+
+pub fn count_in_range_of_strongest(nanobots: &[Nanobot]) -> Result<usize> {
+    let strongest = strongest(nanobots)?;
+    Ok(nanobots
+        .iter()
+        .filter(|nanobot| strongest.in_range(nanobot.position))
+        .count())
+}
+
+/// Finds the position in range of the most nanobots, picking the one closest to the origin if
+/// there's a tie.
+///
+/// This starts with a cube around every nanobot's range and keeps splitting the most promising
+/// cube into octants. A cube is promising if many nanobots reach some part of it, which can only
+/// overestimate how many reach any one position in it, and if it's close to the origin, which can
+/// only underestimate how close any one position in it is. So the first cube of a single position
+/// to come out of the queue can't be beaten by anything left in it.
+pub fn best_position(nanobots: &[Nanobot]) -> Result<Position> {
+    if nanobots.is_empty() {
+        return Err(Error::simulation("There are no nanobots"));
+    }
+    let reach = |axis: fn(Position) -> i64| {
+        let low = nanobots
+            .iter()
+            .map(|nanobot| axis(nanobot.position) - nanobot.radius)
+            .min()
+            .unwrap_or(0);
+        let high = nanobots
+            .iter()
+            .map(|nanobot| axis(nanobot.position) + nanobot.radius)
+            .max()
+            .unwrap_or(0);
+        (low, high)
+    };
+    let (min_x, max_x) = reach(|position| position.x);
+    let (min_y, max_y) = reach(|position| position.y);
+    let (min_z, max_z) = reach(|position| position.z);
+    let span = (max_x - min_x).max(max_y - min_y).max(max_z - min_z) + 1;
+    let mut size = 1;
+    while size < span {
+        size *= 2;
+    }
+
+    let mut queue = BinaryHeap::new();
+    let push = |queue: &mut BinaryHeap<_>, cube: Cube| {
+        let count = nanobots
+            .iter()
+            .filter(|nanobot| nanobot.reaches(&cube))
+            .count();
+        queue.push((
+            count,
+            Reverse(cube.distance(Position::ORIGIN)),
+            Reverse(cube.size),
+            Reverse(cube),
+        ));
+    };
+    push(
+        &mut queue,
+        Cube {
+            min: Position {
+                x: min_x,
+                y: min_y,
+                z: min_z,
+            },
+            size,
+        },
+    );
+    while let Some((_, _, _, Reverse(cube))) = queue.pop() {
+        if cube.size == 1 {
+            return Ok(cube.min);
+        }
+        for octant in cube.split() {
+            push(&mut queue, octant);
+        }
+    }
+    unreachable!("Cubes are split until one of a single position comes out of the queue")
+}
+
+pub fn read_nanobots(input: &Input) -> Result<Vec<Nanobot>> {
+    let input = input.read_to_string()?;
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| line.parse().map_err(|err: Error| err.at_line(index + 1)))
+        .collect()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    count_in_range_of_strongest(&read_nanobots(input)?)
+}
+
+pub fn solve_part2(input: &Input) -> Result<i64> {
+    Ok(best_position(&read_nanobots(input)?)?.distance(Position::ORIGIN))
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed = Vec<Nanobot>;
+
+    fn parse(input: &Input) -> Result<Vec<Nanobot>> {
+        read_nanobots(input)
+    }
+
+    fn part1(nanobots: &Vec<Nanobot>) -> Result<Answer> {
+        Ok(count_in_range_of_strongest(nanobots)?.into())
+    }
+
+    fn part2(nanobots: &Vec<Nanobot>) -> Result<Answer> {
+        Ok(best_position(nanobots)?.distance(Position::ORIGIN).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/23_test.txt";
+    const TEST_INPUT_PART_2: &str = "inputs/23_test_part2.txt";
+
+    #[test]
+    fn reads_nanobots_file() {
+        let nanobots = read_nanobots(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(nanobots.len(), 9);
+        assert_eq!(
+            nanobots[0],
+            Nanobot {
+                position: Position { x: 0, y: 0, z: 0 },
+                radius: 4,
+            }
+        );
+        assert_eq!(
+            "pos=<-3,12,-40>, r=7".parse::<Nanobot>().unwrap(),
+            Nanobot {
+                position: Position {
+                    x: -3,
+                    y: 12,
+                    z: -40
+                },
+                radius: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 7);
+    }
+
+    #[test]
+    fn finds_best_position() {
+        let nanobots = read_nanobots(&Input::file(TEST_INPUT_PART_2)).unwrap();
+        assert_eq!(
+            best_position(&nanobots).unwrap(),
+            Position {
+                x: 12,
+                y: 12,
+                z: 12
+            }
+        );
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT_PART_2)).unwrap(), 36);
+    }
+
+    #[test]
+    fn best_position_matches_checking_every_position() {
+        let nanobots = read_nanobots(&Input::file(TEST_INPUT)).unwrap();
+        let count = |position: Position| {
+            nanobots
+                .iter()
+                .filter(|nanobot| nanobot.in_range(position))
+                .count()
+        };
+        let mut best = (0, Reverse(0));
+        for x in -5..=10 {
+            for y in -5..=10 {
+                for z in -5..=10 {
+                    let position = Position { x, y, z };
+                    best = best.max((
+                        count(position),
+                        Reverse(position.distance(Position::ORIGIN)),
+                    ));
+                }
+            }
+        }
+        let position = best_position(&nanobots).unwrap();
+        assert_eq!(
+            (
+                count(position),
+                Reverse(position.distance(Position::ORIGIN))
+            ),
+            best
+        );
+    }
+
+    #[test]
+    fn errors_on_malformed_nanobot() {
+        match read_nanobots(&Input::text("pos=<0,0,0>, r=4\npos=<1,0>, r=1\n")) {
+            Ok(_) => panic!("read_nanobots should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Malformed nanobot line, expected pos=<x,y,z>, r=<radius>: \"pos=<1,0>, r=1\""
+            ),
+        }
+        assert!(best_position(&[]).is_err());
+    }
+}
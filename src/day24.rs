@@ -0,0 +1,417 @@
+extern crate regex;
+
+use std::cmp::Reverse;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/24.txt";
+const MAX_BOOST: usize = 1 << 30;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Army {
+    ImmuneSystem,
+    Infection,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Group {
+    army: Army,
+    units: usize,
+    hit_points: usize,
+    attack_damage: usize,
+    attack_type: String,
+    initiative: usize,
+    weaknesses: Vec<String>,
+    immunities: Vec<String>,
+}
+
+/// How a battle ended: the army left standing, or `None` if it ended in a stalemate where neither
+/// side could kill any more units, and how many units remain on both sides.
+#[derive(Debug, PartialEq)]
+pub struct Battle {
+    pub winner: Option<Army>,
+    pub units: usize,
+}
+
+impl FromStr for Group {
+    type Err = Error;
+
+    /// Parses everything about a group except which army it belongs to, which comes from the
+    /// heading it is listed under. Groups are read as immune system groups until then.
+    fn from_str(s: &str) -> Result<Group> {
+        lazy_static! {
+            static ref GROUP_REGEX: Regex = Regex::new(concat!(
+                r"^(?P<units>\d+) units each with (?P<hit_points>\d+) hit points ",
+                r"(?:\((?P<modifiers>[^)]*)\) )?",
+                r"with an attack that does (?P<attack_damage>\d+) (?P<attack_type>\w+) damage ",
+                r"at initiative (?P<initiative>\d+)$"
+            ))
+            .unwrap();
+        }
+        let captures = match GROUP_REGEX.captures(s) {
+            Some(captures) => captures,
+            None => {
+                return Err(Error::parse(
+                    1,
+                    s,
+                    "Malformed group line, expected <units> units each with <hit points> hit \
+                     points (<modifiers>) with an attack that does <damage> <type> damage at \
+                     initiative <initiative>",
+                ))
+            }
+        };
+        let mut group = Group {
+            army: Army::ImmuneSystem,
+            units: parse_field(&captures, "units", s)?,
+            hit_points: parse_field(&captures, "hit_points", s)?,
+            attack_damage: parse_field(&captures, "attack_damage", s)?,
+            attack_type: captures["attack_type"].to_string(),
+            initiative: parse_field(&captures, "initiative", s)?,
+            weaknesses: vec![],
+            immunities: vec![],
+        };
+        if let Some(modifiers) = captures.name("modifiers") {
+            let mut column = modifiers.start() + 1;
+            for modifier in modifiers.as_str().split("; ") {
+                let (kinds, types) = if let Some(types) = modifier.strip_prefix("weak to ") {
+                    (&mut group.weaknesses, types)
+                } else if let Some(types) = modifier.strip_prefix("immune to ") {
+                    (&mut group.immunities, types)
+                } else {
+                    return Err(Error::parse(
+                        1,
+                        s,
+                        "Malformed modifier, expected weak to <types> or immune to <types>",
+                    )
+                    .at_column(column));
+                };
+                kinds.extend(types.split(", ").map(str::to_string));
+                column += modifier.len() + "; ".len();
+            }
+        }
+        Ok(group)
+    }
+}
+
+impl Group {
+    fn effective_power(&self) -> usize {
+        self.units * self.attack_damage
+    }
+
+    /// The damage this group would deal to `target`, before accounting for whole units.
+    fn damage_to(&self, target: &Group) -> usize {
+        if target.immunities.contains(&self.attack_type) {
+            0
+        } else if target.weaknesses.contains(&self.attack_type) {
+            self.effective_power() * 2
+        } else {
+            self.effective_power()
+        }
+    }
+}
+
+/// Picks a target for each group: groups choose in decreasing order of effective power and then
+/// initiative, each taking the enemy it would deal the most damage to that hasn't already been
+/// chosen, preferring enemies with more effective power and then initiative. Returns the target
+/// index chosen by each group, if any.
+fn select_targets(groups: &[Group]) -> Vec<Option<usize>> {
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    order
+        .sort_by_key(|&index| Reverse((groups[index].effective_power(), groups[index].initiative)));
+    let mut targets = vec![None; groups.len()];
+    let mut chosen = vec![false; groups.len()];
+    for attacker_index in order {
+        let attacker = &groups[attacker_index];
+        let target = groups
+            .iter()
+            .enumerate()
+            .filter(|(index, target)| target.army != attacker.army && !chosen[*index])
+            .map(|(index, target)| {
+                (
+                    attacker.damage_to(target),
+                    target.effective_power(),
+                    target.initiative,
+                    index,
+                )
+            })
+            .filter(|(damage, ..)| *damage > 0)
+            .max();
+        if let Some((_, _, _, target_index)) = target {
+            chosen[target_index] = true;
+            targets[attacker_index] = Some(target_index);
+        }
+    }
+    targets
+}
+
+/// Runs a round of target selection and attacks, returning how many units were killed.
+fn run_round(groups: &mut Vec<Group>) -> usize {
+    let targets = select_targets(groups);
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    order.sort_by_key(|&index| Reverse(groups[index].initiative));
+    let mut killed = 0;
+    for attacker_index in order {
+        if groups[attacker_index].units == 0 {
+            continue;
+        }
+        if let Some(target_index) = targets[attacker_index] {
+            let damage = groups[attacker_index].damage_to(&groups[target_index]);
+            let target = &mut groups[target_index];
+            let kills = (damage / target.hit_points).min(target.units);
+            target.units -= kills;
+            killed += kills;
+        }
+    }
+    groups.retain(|group| group.units > 0);
+    killed
+}
+
+/// Fights until only one army is left, with the immune system's attack damage raised by
+/// `boost`. The battle ends in a stalemate if a whole round passes without a unit dying, since
+/// every round after it would go the same way.
+pub fn fight(groups: &[Group], boost: usize) -> Battle {
+    let mut groups: Vec<Group> = groups.to_vec();
+    for group in groups.iter_mut() {
+        if group.army == Army::ImmuneSystem {
+            group.attack_damage += boost;
+        }
+    }
+    let winner = loop {
+        let armies_left = |army| groups.iter().any(|group| group.army == army);
+        match (
+            armies_left(Army::ImmuneSystem),
+            armies_left(Army::Infection),
+        ) {
+            (true, true) => {}
+            (true, false) => break Some(Army::ImmuneSystem),
+            (false, true) => break Some(Army::Infection),
+            (false, false) => break None,
+        }
+        if run_round(&mut groups) == 0 {
+            break None;
+        }
+    };
+    Battle {
+        winner,
+        units: groups.iter().map(|group| group.units).sum(),
+    }
+}
+
+/// Finds the smallest boost the immune system needs to win, and the battle it wins with it. This
+/// doubles the boost until the immune system wins and then binary searches between the last boost
+/// that lost and the one that won, which assumes that once a boost is enough to win, every bigger
+/// boost is too.
+pub fn smallest_winning_boost(groups: &[Group]) -> Result<(usize, Battle)> {
+    let wins = |battle: &Battle| battle.winner == Some(Army::ImmuneSystem);
+    let battle = fight(groups, 0);
+    if wins(&battle) {
+        return Ok((0, battle));
+    }
+    let mut losing = 0;
+    let mut winning = 1;
+    let mut winning_battle = loop {
+        let battle = fight(groups, winning);
+        if wins(&battle) {
+            break battle;
+        }
+        if winning >= MAX_BOOST {
+            return Err(Error::simulation(format!(
+                "The immune system does not win even with a boost of {}",
+                MAX_BOOST
+            )));
+        }
+        losing = winning;
+        winning *= 2;
+    };
+    while winning - losing > 1 {
+        let boost = losing + (winning - losing) / 2;
+        let battle = fight(groups, boost);
+        if wins(&battle) {
+            winning = boost;
+            winning_battle = battle;
+        } else {
+            losing = boost;
+        }
+    }
+    Ok((winning, winning_battle))
+}
+
+pub fn read_groups(input: &Input) -> Result<Vec<Group>> {
+    let input = input.read_to_string()?;
+    let mut groups = vec![];
+    let mut army = None;
+    for (index, line) in input.lines().enumerate() {
+        match line.trim_end() {
+            "" => {}
+            "Immune System:" => army = Some(Army::ImmuneSystem),
+            "Infection:" => army = Some(Army::Infection),
+            line => {
+                let army = army.ok_or_else(|| {
+                    Error::parse(
+                        index + 1,
+                        line,
+                        "Group listed before an Immune System: or Infection: heading",
+                    )
+                })?;
+                let group: Group = line.parse().map_err(|err: Error| err.at_line(index + 1))?;
+                groups.push(Group { army, ..group });
+            }
+        }
+    }
+    Ok(groups)
+}
+
+fn remaining_units(battle: Battle) -> Result<usize> {
+    match battle.winner {
+        Some(_) => Ok(battle.units),
+        None => Err(Error::simulation(format!(
+            "The battle ended in a stalemate with {} units left",
+            battle.units
+        ))),
+    }
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    remaining_units(fight(&read_groups(input)?, 0))
+}
+
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    Ok(smallest_winning_boost(&read_groups(input)?)?.1.units)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed = Vec<Group>;
+
+    fn parse(input: &Input) -> Result<Vec<Group>> {
+        read_groups(input)
+    }
+
+    fn part1(groups: &Vec<Group>) -> Result<Answer> {
+        Ok(remaining_units(fight(groups, 0))?.into())
+    }
+
+    fn part2(groups: &Vec<Group>) -> Result<Answer> {
+        Ok(smallest_winning_boost(groups)?.1.units.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/24_test.txt";
+    const STALEMATE_INPUT: &str = "Immune System:\n\
+        10 units each with 100 hit points (immune to fire) with an attack that does 5 cold damage at initiative 2\n\
+        \n\
+        Infection:\n\
+        10 units each with 100 hit points with an attack that does 5 fire damage at initiative 1\n";
+
+    #[test]
+    fn reads_groups_file() {
+        let groups = read_groups(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(groups.len(), 4);
+        assert_eq!(
+            groups[1],
+            Group {
+                army: Army::ImmuneSystem,
+                units: 989,
+                hit_points: 1274,
+                attack_damage: 25,
+                attack_type: "slashing".to_string(),
+                initiative: 3,
+                weaknesses: vec!["bludgeoning".to_string(), "slashing".to_string()],
+                immunities: vec!["fire".to_string()],
+            }
+        );
+        assert_eq!(groups[2].army, Army::Infection);
+        assert_eq!(groups[2].weaknesses, vec!["radiation".to_string()]);
+    }
+
+    #[test]
+    fn selects_targets() {
+        let groups = read_groups(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(
+            select_targets(&groups),
+            vec![Some(3), Some(2), Some(0), Some(1)]
+        );
+    }
+
+    #[test]
+    fn fights_a_round() {
+        let mut groups = read_groups(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(run_round(&mut groups), 17 + 84 + 4 + 51);
+        let units: Vec<usize> = groups.iter().map(|group| group.units).collect();
+        assert_eq!(units, vec![905, 797, 4434]);
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 5216);
+    }
+
+    #[test]
+    fn fights_with_a_boost() {
+        let groups = read_groups(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(
+            fight(&groups, 1570),
+            Battle {
+                winner: Some(Army::ImmuneSystem),
+                units: 51,
+            }
+        );
+        assert_eq!(smallest_winning_boost(&groups).unwrap().0, 1570);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(solve_part2(&Input::file(TEST_INPUT)).unwrap(), 51);
+    }
+
+    #[test]
+    fn detects_stalemates() {
+        let input = Input::text(STALEMATE_INPUT);
+        assert_eq!(
+            fight(&read_groups(&input).unwrap(), 0),
+            Battle {
+                winner: None,
+                units: 20,
+            }
+        );
+        assert!(solve_part1(&input).is_err());
+    }
+
+    #[test]
+    fn finds_smallest_winning_boost() {
+        let groups = read_groups(&Input::text(STALEMATE_INPUT)).unwrap();
+        let smallest = (0..)
+            .find(|boost| fight(&groups, *boost).winner == Some(Army::ImmuneSystem))
+            .unwrap();
+        assert_eq!(smallest_winning_boost(&groups).unwrap().0, smallest);
+    }
+
+    #[test]
+    fn errors_on_malformed_group() {
+        match read_groups(&Input::text(
+            "Immune System:\n17 units each with 5390 hit points (fond of fire) with an attack that does 4507 fire damage at initiative 2\n",
+        )) {
+            Ok(_) => panic!("read_groups should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2, column 37: Malformed modifier, expected weak to <types> or immune to \
+                 <types>: \"17 units each with 5390 hit points (fond of fire) with an attack \
+                 that does 4507 fire damage at initiative 2\""
+            ),
+        }
+        assert!(read_groups(&Input::text(
+            "17 units each with 5390 hit points with an attack that does 4507 fire damage at initiative 2\n"
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use crate::disjoint_sets::DisjointSets;
+use crate::error::{Error, ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/25.txt";
+const CONSTELLATION_DISTANCE: i32 = 3;
+
+pub type Point = [i32; 4];
+
+/// The Manhattan distance between two points, in an `i64` since points at opposite ends of the
+/// `i32` range are further apart than an `i32` can hold.
+fn distance(a: &Point, b: &Point) -> i64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| (i64::from(*a) - i64::from(*b)).abs())
+        .sum()
+}
+
+/// The bucket a point falls in when space is divided into cubes as wide as the constellation
+/// distance. Points close enough to join a constellation are always in the same or adjacent
+/// buckets along every axis.
+fn bucket(point: &Point) -> Point {
+    let mut bucket = [0; 4];
+    for (axis, coordinate) in point.iter().enumerate() {
+        bucket[axis] = coordinate.div_euclid(CONSTELLATION_DISTANCE);
+    }
+    bucket
+}
+
+/// The bucket itself and the 80 buckets adjacent to it.
+fn neighboring_buckets(bucket: &Point) -> Vec<Point> {
+    let mut buckets = vec![*bucket];
+    for axis in 0..4 {
+        buckets = buckets
+            .into_iter()
+            .flat_map(|neighbor| {
+                (-1..=1).map(move |offset| {
+                    let mut neighbor = neighbor;
+                    neighbor[axis] += offset;
+                    neighbor
+                })
+            })
+            .collect();
+    }
+    buckets
+}
+
+/// Joins every pair of points close enough to be in the same constellation and counts the
+/// constellations that makes. Points are only compared against the others in their own and
+/// adjacent buckets, rather than against every other point.
+pub fn count_constellations(points: &[Point]) -> usize {
+    let mut buckets: HashMap<Point, Vec<usize>> = HashMap::new();
+    for (index, point) in points.iter().enumerate() {
+        buckets.entry(bucket(point)).or_default().push(index);
+    }
+    let mut constellations = DisjointSets::new(points.len());
+    for (index, point) in points.iter().enumerate() {
+        for neighbor in neighboring_buckets(&bucket(point)) {
+            if let Some(others) = buckets.get(&neighbor) {
+                for &other in others {
+                    if other < index
+                        && distance(point, &points[other]) <= i64::from(CONSTELLATION_DISTANCE)
+                    {
+                        constellations.union(index, other);
+                    }
+                }
+            }
+        }
+    }
+    constellations.count()
+}
+
+pub fn read_points(input: &Input) -> Result<Vec<Point>> {
+    let input = input.read_to_string()?;
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let coordinates = line
+                .split(',')
+                .map(|coordinate| coordinate.trim().parse().on_line(index + 1, line))
+                .collect::<Result<Vec<i32>>>()?;
+            match coordinates[..] {
+                [x, y, z, t] => Ok([x, y, z, t]),
+                _ => Err(Error::parse(
+                    index + 1,
+                    line,
+                    format!("Point has {} coordinates, expected 4", coordinates.len()),
+                )),
+            }
+        })
+        .collect()
+}
+
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    Ok(count_constellations(&read_points(input)?))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed = Vec<Point>;
+
+    fn parse(input: &Input) -> Result<Vec<Point>> {
+        read_points(input)
+    }
+
+    fn part1(points: &Vec<Point>) -> Result<Answer> {
+        Ok(count_constellations(points).into())
+    }
+
+    /// The last day only has the one puzzle.
+    fn part2(_: &Vec<Point>) -> Result<Answer> {
+        Ok(Answer::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+
+    const TEST_INPUT: &str = "inputs/25_test.txt";
+    const TEST_INPUT_2: &str = "inputs/25_test_2.txt";
+    const TEST_INPUT_3: &str = "inputs/25_test_3.txt";
+    const TEST_INPUT_4: &str = "inputs/25_test_4.txt";
+
+    fn count_constellations_pairwise(points: &[Point]) -> usize {
+        let mut constellations = DisjointSets::new(points.len());
+        for a in 0..points.len() {
+            for b in 0..a {
+                if distance(&points[a], &points[b]) <= i64::from(CONSTELLATION_DISTANCE) {
+                    constellations.union(a, b);
+                }
+            }
+        }
+        constellations.count()
+    }
+
+    #[test]
+    fn reads_points_file() {
+        let points = read_points(&Input::file(TEST_INPUT)).unwrap();
+        assert_eq!(points.len(), 8);
+        assert_eq!(points[7], [12, 0, 0, 0]);
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT)).unwrap(), 2);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_2)).unwrap(), 4);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_3)).unwrap(), 3);
+        assert_eq!(solve_part1(&Input::file(TEST_INPUT_4)).unwrap(), 8);
+    }
+
+    #[test]
+    fn buckets_match_comparing_every_pair() {
+        // Scatters points over spans dense enough that constellations chain across buckets and
+        // sparse enough that there are many of them.
+        let mut lcg = Lcg::new(12345);
+        for &span in [4, 6, 8].iter() {
+            let points: Vec<Point> = (0..500)
+                .map(|_| {
+                    [
+                        lcg.within(span),
+                        lcg.within(span),
+                        lcg.within(span),
+                        lcg.within(span),
+                    ]
+                })
+                .collect();
+            assert_eq!(
+                count_constellations(&points),
+                count_constellations_pairwise(&points)
+            );
+        }
+    }
+
+    #[test]
+    fn counts_points_at_the_ends_of_the_range() {
+        let points = read_points(&Input::text(
+            "-2147483648,0,0,0\n2147483647,0,0,0\n2147483647,2147483647,2147483647,2147483647\n",
+        ))
+        .unwrap();
+        assert_eq!(count_constellations(&points), 3);
+        assert_eq!(count_constellations_pairwise(&points), 3);
+    }
+
+    #[test]
+    fn errors_on_malformed_point() {
+        match read_points(&Input::text("0,0,0,0\n3,0,0\n")) {
+            Ok(_) => panic!("read_points should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Point has 3 coordinates, expected 4: \"3,0,0\""
+            ),
+        }
+        assert!(read_points(&Input::text("0,0,x,0\n")).is_err());
+    }
+}
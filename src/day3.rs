@@ -1,18 +1,19 @@
 extern crate regex;
 
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::fmt;
-use std::collections::{HashMap, HashSet};
-use std::iter::FromIterator;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::collections::HashMap;
 
-use regex::{Regex, Captures};
+use regex::Regex;
 
-const INPUT: &str = "inputs/3.txt";
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/3.txt";
 
 #[derive(Debug, PartialEq, Clone)]
-struct Claim {
+pub struct Claim {
     id: u32,
     left: u32,
     top: u32,
@@ -21,45 +22,43 @@ struct Claim {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Point {
+pub struct Point {
     x: u32,
     y: u32,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct MalformedClaim {
-    details: String
-}
-
-impl MalformedClaim {
-    fn new(msg: &str) -> MalformedClaim {
-        MalformedClaim{ details: msg.to_string() }
-    }
-}
+impl FromStr for Claim {
+    type Err = Error;
 
-impl fmt::Display for MalformedClaim {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
-    }
-}
-
-impl Error for MalformedClaim {
-    fn description(&self) -> &str {
-        &self.details
+    fn from_str(s: &str) -> Result<Claim> {
+        lazy_static! {
+            static ref CLAIM_REGEX: Regex = Regex::new(
+                r"#(?P<id>\d+) @ (?P<left>\d+),(?P<top>\d+): (?P<width>\d+)x(?P<height>\d+)").unwrap();
+        }
+        match CLAIM_REGEX.captures(s) {
+            Some(captures) => Ok(Claim {
+                id: parse_field(&captures, "id", s)?,
+                left: parse_field(&captures, "left", s)?,
+                top: parse_field(&captures, "top", s)?,
+                width: parse_field(&captures, "width", s)?,
+                height: parse_field(&captures, "height", s)?,
+            }),
+            None => Err(Error::parse(1, s, "Malformed claim line, no fields could be found")),
+        }
     }
 }
 
-pub fn solve_part1() -> Result<u32, Box<Error>> {
-    Ok(count_overlapping_claimed_points(read_claims(INPUT)?))
+pub fn solve_part1(input: &Input) -> Result<u32> {
+    Ok(count_overlapping_claimed_points(&read_claims(input)?))
 }
 
-pub fn solve_part2() -> Result<Option<u32>, Box<Error>> {
-    Ok(find_non_overlapping_claim(read_claims(INPUT)?))
+pub fn solve_part2(input: &Input) -> Result<Option<u32>> {
+    Ok(find_non_overlapping_claim(&read_claims(input)?))
 }
 
-fn count_overlapping_claimed_points(claims: Vec<Claim>) -> u32 {
-    let claimed_points = get_claimed_points(&claims);
-    return claimed_points.values().fold(0, |acc, claims| {
+pub fn count_overlapping_claimed_points(claims: &[Claim]) -> u32 {
+    let claimed_points = get_claimed_points(claims);
+    claimed_points.values().fold(0, |acc, claims| {
         if claims > &1 {
             return acc + 1
         }
@@ -67,12 +66,12 @@ fn count_overlapping_claimed_points(claims: Vec<Claim>) -> u32 {
     })
 }
 
-fn find_non_overlapping_claim(claims: Vec<Claim>) -> Option<u32> {
-    let claimed_points = get_claimed_points(&claims);
+pub fn find_non_overlapping_claim(claims: &[Claim]) -> Option<u32> {
+    let claimed_points = get_claimed_points(claims);
     for claim in claims {
-        let points = list_points_in_claim(&claim);
+        let points = list_points_in_claim(claim);
         let non_overlapping = points.iter().all(
-            |point| claimed_points.get(&point).unwrap_or(&0) < &2);
+            |point| claimed_points.get(point).unwrap_or(&0) < &2);
         if non_overlapping {
             return Some(claim.id);
         }
@@ -80,10 +79,10 @@ fn find_non_overlapping_claim(claims: Vec<Claim>) -> Option<u32> {
     None
 }
 
-fn get_claimed_points(claims: &Vec<Claim>) -> HashMap<Point, u32> {
+fn get_claimed_points(claims: &[Claim]) -> HashMap<Point, u32> {
     let mut claimed_points: HashMap<Point, u32> = HashMap::new();
     for claim in claims {
-        for point in list_points_in_claim(&claim) {
+        for point in list_points_in_claim(claim) {
             let current_point = claimed_points.get(&point).unwrap_or(&0);
             claimed_points.insert(point, current_point + 1);
         }
@@ -101,40 +100,31 @@ fn list_points_in_claim(claim: &Claim) -> Vec<Point> {
     points
 }
 
-fn read_claims(filename: &str) -> Result<Vec<Claim>, Box<Error>> {
+pub fn read_claims(input: &Input) -> Result<Vec<Claim>> {
     let mut claims: Vec<Claim> = Vec::new();
-    let claim_regex =
-        Regex::new(r"#(?P<id>\d+) @ (?P<left>\d+),(?P<top>\d+): (?P<width>\d+)x(?P<height>\d+)")?;
-    let file = File::open(filename)?;
-    for line in BufReader::new(file).lines() {
-        match claim_regex.captures(&line?) {
-            Some(captures) => {
-                let claim = Claim {
-                    id: get_captured_field(&captures, "id")?,
-                    left: get_captured_field(&captures, "left")?,
-                    top: get_captured_field(&captures, "top")?,
-                    width: get_captured_field(&captures, "width")?,
-                    height: get_captured_field(&captures, "height")?,
-                };
-                claims.push(claim);
-            },
-            None => return Err(Box::new(MalformedClaim {
-                details: "Malformed claim line, no fields could be found".to_string()
-            })),
-        };
+    for (index, line) in input.reader()?.lines().enumerate() {
+        claims.push(line?.parse().map_err(|err: Error| err.at_line(index + 1))?);
     }
     Ok(claims)
 }
 
-fn get_captured_field(captures: &Captures, field: &str) -> Result<u32, Box<Error>> {
-    match captures.name(field) {
-        Some(capture) => Ok(capture.as_str().parse()?),
-        None => return Err(Box::new(MalformedClaim {
-            details: format!("Malformed claim line, field {} could not be found", field)
-        }))
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Vec<Claim>;
+
+    fn parse(input: &Input) -> Result<Vec<Claim>> {
+        read_claims(input)
     }
-}
 
+    fn part1(claims: &Vec<Claim>) -> Result<Answer> {
+        Ok(count_overlapping_claimed_points(claims).into())
+    }
+
+    fn part2(claims: &Vec<Claim>) -> Result<Answer> {
+        Ok(find_non_overlapping_claim(claims).into())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -145,7 +135,7 @@ mod tests {
 
     #[test]
     fn reads_claims_file() {
-        assert_eq!(read_claims(TEST_INPUT).unwrap(), vec![
+        assert_eq!(read_claims(&Input::file(TEST_INPUT)).unwrap(), vec![
             Claim { id: 1, left: 1, top: 3, width: 4, height: 4 },
             Claim { id: 2, left: 3, top: 1, width: 4, height: 4 },
             Claim { id: 3, left: 5, top: 5, width: 2, height: 2 },
@@ -154,11 +144,11 @@ mod tests {
 
     #[test]
     fn errors_on_malformed_claims_file() {
-        match read_claims(TEST_INPUT_MALFORMED) {
-            Ok(_) => assert!(false, "read_claims should have returned an error"),
+        match read_claims(&Input::file(TEST_INPUT_MALFORMED)) {
+            Ok(_) => panic!("read_claims should have returned an error"),
             Err(err) => assert_eq!(
-                (*err).description(),
-                "Malformed claim line, no fields could be found".to_string(),
+                err.to_string(),
+                "line 2: Malformed claim line, no fields could be found: \"#2 @  3,1: 4x4\"",
             ),
         }
     }
@@ -182,7 +172,7 @@ mod tests {
             Claim { id: 2, left: 3, top: 1, width: 4, height: 4 },
             Claim { id: 3, left: 5, top: 5, width: 2, height: 2 },
         ];
-        assert_eq!(count_overlapping_claimed_points(test_claims), 4);
+        assert_eq!(count_overlapping_claimed_points(&test_claims), 4);
     }
 
     #[test]
@@ -192,6 +182,6 @@ mod tests {
             Claim { id: 2, left: 3, top: 1, width: 4, height: 4 },
             Claim { id: 3, left: 5, top: 5, width: 2, height: 2 },
         ];
-        assert_eq!(find_non_overlapping_claim(test_claims).unwrap(), 3);
+        assert_eq!(find_non_overlapping_claim(&test_claims).unwrap(), 3);
     }
 }
@@ -1,21 +1,22 @@
 extern crate chrono;
 extern crate regex;
 
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::fmt;
-use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::str::FromStr;
+use std::collections::HashMap;
 use std::collections::hash_map::Entry;
-use std::iter::FromIterator;
 
 use chrono::prelude::*;
-use regex::{Regex, Captures};
+use regex::Regex;
 
-const INPUT: &str = "inputs/4.txt";
+use crate::error::{parse_field, Error, ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/4.txt";
 
 #[derive(Debug, PartialEq)]
-enum Record {
+pub enum Record {
     Start {
         time: NaiveDateTime,
         guard_id: u32,
@@ -38,47 +39,70 @@ impl Record {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct MalformedRecord {
-    details: String
-}
+impl FromStr for Record {
+    type Err = Error;
 
-impl MalformedRecord {
-    fn new(msg: &str) -> MalformedRecord {
-        MalformedRecord{ details: msg.to_string() }
+    fn from_str(s: &str) -> Result<Record> {
+        lazy_static! {
+            static ref RECORD_REGEX: Regex = Regex::new(concat!(
+                r"\[(?P<timestamp>\d{4}-\d{2}-\d{2}\s\d{2}:\d{2})\]\s(?:",
+                r"(?P<start>Guard #(?P<guard_id>\d+) begins shift)|",
+                r"(?P<sleep>falls asleep)|",
+                r"(?P<wake>wakes up))")).unwrap();
+        }
+        match RECORD_REGEX.captures(s) {
+            Some(captures) => {
+                let timestamp = captures.name("timestamp").unwrap();
+                let time = NaiveDateTime::parse_from_str(timestamp.as_str(), "%Y-%m-%d %H:%M")
+                    .on_line(1, s)
+                    .map_err(|err| err.at_column(timestamp.start() + 1))?;
+                if captures.name("start").is_some() {
+                    Ok(Record::Start {
+                        time,
+                        guard_id: parse_field(&captures, "guard_id", s)?,
+                    })
+                } else if captures.name("sleep").is_some() {
+                    Ok(Record::Sleep {
+                        time,
+                    })
+                } else {
+                    Ok(Record::Wake {
+                        time,
+                    })
+                }
+            },
+            None => Err(Error::parse(1, s, "Malformed record line, no fields could be found")),
+        }
     }
 }
 
-impl fmt::Display for MalformedRecord {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
-    }
+pub fn solve_part1(input: &Input) -> Result<u32> {
+    get_part1(input)
 }
 
-impl Error for MalformedRecord {
-    fn description(&self) -> &str {
-        &self.details
-    }
+pub fn solve_part2(input: &Input) -> Result<u32> {
+    get_part2(input)
 }
 
-pub fn solve_part1() -> Result<u32, Box<Error>> {
-    Ok(get_part1(INPUT)?)
+fn get_part1(input: &Input) -> Result<u32> {
+    sleepiest_guard_times_minute(&read_records(input)?)
 }
 
-pub fn solve_part2() -> Result<u32, Box<Error>> {
-    Ok(get_part2(INPUT)?)
+fn get_part2(input: &Input) -> Result<u32> {
+    most_frequently_asleep_guard_times_minute(&read_records(input)?)
 }
 
-fn get_part1(filename: &str) -> Result<u32, Box<Error>> {
-    let records = read_records(filename)?;
+pub fn sleepiest_guard_times_minute(records: &[Record]) -> Result<u32> {
     let minutes_asleep = minutes_asleep_per_guard(records);
-    let sleepiest_guard = minutes_asleep.iter().max_by_key(|&(_, mins)| mins.len()).unwrap();
+    let sleepiest_guard = minutes_asleep
+        .iter()
+        .max_by_key(|&(_, mins)| mins.len())
+        .ok_or_else(|| Error::simulation("No guard records"))?;
     let sleepiest_minute = mode(sleepiest_guard.1);
     Ok(sleepiest_guard.0 * sleepiest_minute)
 }
 
-fn get_part2(filename: &str) -> Result<u32, Box<Error>> {
-    let records = read_records(filename)?;
+pub fn most_frequently_asleep_guard_times_minute(records: &[Record]) -> Result<u32> {
     let minutes_asleep = minutes_asleep_per_guard(records);
     let all_mins: Vec<u32> = minutes_asleep
         .values()
@@ -88,8 +112,8 @@ fn get_part2(filename: &str) -> Result<u32, Box<Error>> {
     let sleepiest_minute = mode(&all_mins[..]);
     let sleepiest_guard = minutes_asleep
         .iter()
-        .max_by_key(|(_, mins)| mins.into_iter().filter(|min| **min == sleepiest_minute).count())
-        .unwrap();
+        .max_by_key(|(_, mins)| mins.iter().filter(|min| **min == sleepiest_minute).count())
+        .ok_or_else(|| Error::simulation("No guard records"))?;
     Ok(sleepiest_guard.0 * sleepiest_minute)
 }
 
@@ -105,13 +129,14 @@ fn mode(numbers: &[u32]) -> u32 {
         .unwrap_or(0)
 }
 
-fn minutes_asleep_per_guard(mut records: Vec<Record>) -> HashMap<u32, Vec<u32>> {
+pub fn minutes_asleep_per_guard(records: &[Record]) -> HashMap<u32, Vec<u32>> {
     let mut minutes_asleep: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut records: Vec<&Record> = records.iter().collect();
     records.sort_by_key(|r| r.time());
     let mut current_guard = 0;
     let mut fell_asleep = 0;
     for record in records {
-        match record {
+        match *record {
             Record::Start { time: _, guard_id } => current_guard = guard_id,
             Record::Sleep { time } => fell_asleep = time.minute(),
             Record::Wake { time } => {
@@ -126,60 +151,31 @@ fn minutes_asleep_per_guard(mut records: Vec<Record>) -> HashMap<u32, Vec<u32>>
     minutes_asleep
 }
 
-fn read_records(filename: &str) -> Result<Vec<Record>, Box<Error>> {
+pub fn read_records(input: &Input) -> Result<Vec<Record>> {
     let mut records: Vec<Record> = Vec::new();
-    let record_regex =
-        Regex::new(concat!(
-            r"\[(?P<timestamp>\d{4}-\d{2}-\d{2}\s\d{2}:\d{2})\]\s(?:",
-            r"(?P<start>Guard #(?P<guard_id>\d+) begins shift)|",
-            r"(?P<sleep>falls asleep)|",
-            r"(?P<wake>wakes up))"))?;
-    let file = File::open(filename)?;
-    for line in BufReader::new(file).lines() {
-        match record_regex.captures(&line?) {
-            Some(captures) => {
-                let time = NaiveDateTime::parse_from_str(
-                    &get_captured_field(&captures, "timestamp")?,
-                    "%Y-%m-%d %H:%M")?;
-                if has_captured_field(&captures, "start")? {
-                    records.push(Record::Start {
-                        time: time,
-                        guard_id: get_captured_field(&captures, "guard_id")?.parse()?,
-                    });
-                } else if has_captured_field(&captures, "sleep")? {
-                    records.push(Record::Sleep {
-                        time: time,
-                    });
-                } else {
-                    records.push(Record::Wake {
-                        time: time,
-                    });
-                }
-            },
-            None => return Err(Box::new(MalformedRecord {
-                details: "Malformed record line, no fields could be found".to_string()
-            })),
-        };
+    for (index, line) in input.reader()?.lines().enumerate() {
+        records.push(line?.parse().map_err(|err: Error| err.at_line(index + 1))?);
     }
     Ok(records)
 }
 
-fn get_captured_field(captures: &Captures, field: &str) -> Result<String, Box<Error>> {
-    match captures.name(field) {
-        Some(capture) => Ok(String::from(capture.as_str())),
-        None => return Err(Box::new(MalformedRecord {
-            details: format!("Malformed record line, field {} could not be found", field)
-        }))
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Record>;
+
+    fn parse(input: &Input) -> Result<Vec<Record>> {
+        read_records(input)
     }
-}
 
-fn has_captured_field(captures: &Captures, field: &str) -> Result<bool, Box<Error>> {
-    match captures.name(field) {
-        Some(_) => Ok(true),
-        None => Ok(false)
+    fn part1(records: &Vec<Record>) -> Result<Answer> {
+        Ok(sleepiest_guard_times_minute(records)?.into())
     }
-}
 
+    fn part2(records: &Vec<Record>) -> Result<Answer> {
+        Ok(most_frequently_asleep_guard_times_minute(records)?.into())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -190,7 +186,7 @@ mod tests {
 
     #[test]
     fn reads_records_file() {
-        assert_eq!(read_records(TEST_INPUT).unwrap(), vec![
+        assert_eq!(read_records(&Input::file(TEST_INPUT)).unwrap(), vec![
             Record::Start {
                 time: NaiveDateTime::parse_from_str(
                           "1518-11-01 00:00", "%Y-%m-%d %H:%M").unwrap(),
@@ -269,11 +265,11 @@ mod tests {
 
     #[test]
     fn errors_on_malformed_records_file() {
-        match read_records(TEST_INPUT_MALFORMED) {
-            Ok(_) => assert!(false, "read_records should have returned an error"),
+        match read_records(&Input::file(TEST_INPUT_MALFORMED)) {
+            Ok(_) => panic!("read_records should have returned an error"),
             Err(err) => assert_eq!(
-                (*err).description(),
-                "Malformed record line, no fields could be found".to_string(),
+                err.to_string(),
+                "line 5: Malformed record line, no fields could be found: \"[1518-11-01 00:55] dies\"",
             ),
         }
     }
@@ -282,7 +278,7 @@ mod tests {
     fn gets_minutes_asleep_per_guard() {
         let mut expected: HashMap<u32, Vec<u32>> = HashMap::new();
         expected.insert(10, vec![5, 6, 7, 8, 9]);
-        assert_eq!(minutes_asleep_per_guard(vec![
+        assert_eq!(minutes_asleep_per_guard(&[
             Record::Sleep {
                 time: NaiveDateTime::parse_from_str(
                           "1518-11-01 00:05", "%Y-%m-%d %H:%M").unwrap(),
@@ -301,11 +297,20 @@ mod tests {
 
     #[test]
     fn solves_part1() {
-        assert_eq!(get_part1(TEST_INPUT).unwrap(), 240);
+        assert_eq!(get_part1(&Input::file(TEST_INPUT)).unwrap(), 240);
     }
 
     #[test]
     fn solves_part2() {
-        assert_eq!(get_part2(TEST_INPUT).unwrap(), 4455);
+        assert_eq!(get_part2(&Input::file(TEST_INPUT)).unwrap(), 4455);
+    }
+
+    #[test]
+    fn errors_on_no_records() {
+        match get_part1(&Input::text("")) {
+            Ok(_) => panic!("get_part1 should have returned an error"),
+            Err(err) => assert_eq!(err.to_string(), "No guard records"),
+        }
+        assert!(get_part2(&Input::text("")).is_err());
     }
 }
@@ -1,32 +1,33 @@
 extern crate regex;
 
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::collections::HashMap;
 
 use regex::Regex;
 
-const INPUT: &str = "inputs/5.txt";
+use crate::error::Result;
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub const INPUT: &str = "inputs/5.txt";
 const UNITS: &str = "abcdefghijklmnopqrstuvwxyz";
 
-pub fn solve_part1() -> Result<usize, Box<Error>> {
-    let polymer = read_polymer(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    let polymer = read_polymer(input)?;
     Ok(reduce_polymer_completely(polymer).len())
 }
 
-pub fn solve_part2() -> Result<usize, Box<Error>> {
-    let polymer = read_polymer(INPUT)?;
-    Ok(find_shortest_unit_eliminated_polymer(polymer))
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    let polymer = read_polymer(input)?;
+    Ok(find_shortest_unit_eliminated_polymer(&polymer))
 }
 
-fn read_polymer(filename: &str) -> Result<String, Box<Error>> {
-    let file = File::open(filename)?;
-    let polymer = BufReader::new(file).lines().next().unwrap_or(Ok("".to_string()));
+pub fn read_polymer(input: &Input) -> Result<String> {
+    let polymer = input.reader()?.lines().next().unwrap_or(Ok("".to_string()));
     Ok(polymer?)
 }
 
-fn reduce_polymer(polymer: &String) -> String {
+pub fn reduce_polymer(polymer: &str) -> String {
     lazy_static! {
         static ref REACTING_UNITS: Regex = Regex::new(concat!(
             r"aA|bB|cC|dD|eE|fF|gG|hH|iI|jJ|kK|lL|mM|nN|",
@@ -34,10 +35,10 @@ fn reduce_polymer(polymer: &String) -> String {
             r"Aa|Bb|Cc|Dd|Ee|Ff|Gg|Hh|Ii|Jj|Kk|Ll|Mm|Nn|",
             r"Oo|Pp|Qq|Rr|Ss|Tt|Uu|Vv|Ww|Xx|Yy|Zz")).unwrap();
     }
-    REACTING_UNITS.replace_all(&polymer, "").to_string()
+    REACTING_UNITS.replace_all(polymer, "").to_string()
 }
 
-fn reduce_polymer_completely(polymer: String) -> String {
+pub fn reduce_polymer_completely(polymer: String) -> String {
     let reduced = reduce_polymer(&polymer);
     if reduced == polymer {
         reduced
@@ -46,17 +47,34 @@ fn reduce_polymer_completely(polymer: String) -> String {
     }
 }
 
-fn find_shortest_unit_eliminated_polymer(polymer: String) -> usize {
+pub fn find_shortest_unit_eliminated_polymer(polymer: &str) -> usize {
     let mut eliminated_unit_polymers = HashMap::new();
     for unit in UNITS.chars() {
         let test_polymer = polymer
-            .replace(unit, "")
-            .replace(unit.to_ascii_uppercase(), "");
+            .replace([unit, unit.to_ascii_uppercase()], "");
         eliminated_unit_polymers.insert(unit, reduce_polymer_completely(test_polymer).len());
     }
     *eliminated_unit_polymers.iter().min_by_key(|&(_, len)| len).unwrap().1
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = String;
+
+    fn parse(input: &Input) -> Result<String> {
+        read_polymer(input)
+    }
+
+    fn part1(polymer: &String) -> Result<Answer> {
+        Ok(reduce_polymer_completely(polymer.clone()).len().into())
+    }
+
+    fn part2(polymer: &String) -> Result<Answer> {
+        Ok(find_shortest_unit_eliminated_polymer(polymer).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,14 +83,14 @@ mod tests {
 
     #[test]
     fn reduces_polymer() {
-        assert_eq!(reduce_polymer(&"aA".to_string()), "");
-        assert_eq!(reduce_polymer(&"aAbB".to_string()), "");
-        assert_eq!(reduce_polymer(&"aAfgbB".to_string()), "fg");
-        assert_eq!(reduce_polymer(&"abAB".to_string()), "abAB");
-        assert_eq!(reduce_polymer(&"aabAAB".to_string()), "aabAAB");
-        assert_eq!(reduce_polymer(&"dabAcCaCBAcCcaDA".to_string()), "dabAaCBAcaDA");
-        assert_eq!(reduce_polymer(&"dabAaCBAcCcaDA".to_string()), "dabCBAcaDA");
-        assert_eq!(reduce_polymer(&"dabCBAcCcaDA".to_string()), "dabCBAcaDA");
+        assert_eq!(reduce_polymer("aA"), "");
+        assert_eq!(reduce_polymer("aAbB"), "");
+        assert_eq!(reduce_polymer("aAfgbB"), "fg");
+        assert_eq!(reduce_polymer("abAB"), "abAB");
+        assert_eq!(reduce_polymer("aabAAB"), "aabAAB");
+        assert_eq!(reduce_polymer("dabAcCaCBAcCcaDA"), "dabAaCBAcaDA");
+        assert_eq!(reduce_polymer("dabAaCBAcCcaDA"), "dabCBAcaDA");
+        assert_eq!(reduce_polymer("dabCBAcCcaDA"), "dabCBAcaDA");
     }
 
     #[test]
@@ -82,11 +100,11 @@ mod tests {
 
     #[test]
     fn reads_polymer() {
-        assert_eq!(read_polymer(TEST_INPUT).unwrap(), "dabAcCaCBAcCcaDA");
+        assert_eq!(read_polymer(&Input::file(TEST_INPUT)).unwrap(), "dabAcCaCBAcCcaDA");
     }
 
     #[test]
     fn finds_shortest_unit_eliminated_polymer() {
-        assert_eq!(find_shortest_unit_eliminated_polymer("dabAcCaCBAcCcaDA".to_string()), 4);
+        assert_eq!(find_shortest_unit_eliminated_polymer("dabAcCaCBAcCcaDA"), 4);
     }
 }
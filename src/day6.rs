@@ -1,12 +1,14 @@
 extern crate regex;
 
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::fmt;
 use std::collections::{HashMap, HashSet};
 
-use regex::{Regex, Captures};
+use regex::Regex;
+
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
 static ALPHABET: [char; 52] = [
     'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j',
@@ -16,17 +18,17 @@ static ALPHABET: [char; 52] = [
     'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T',
     'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
-const INPUT: &str = "inputs/6.txt";
+pub const INPUT: &str = "inputs/6.txt";
 
 #[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
-struct Coordinate {
+pub struct Coordinate {
     x: u32,
     y: u32,
     letter: char,
 }
 
 #[derive(Debug, PartialEq)]
-enum GridPoint {
+pub enum GridPoint {
     Unfilled {
         x: u32,
         y: u32,
@@ -45,7 +47,7 @@ enum GridPoint {
 }
 
 #[derive(Debug, PartialEq)]
-struct Grid {
+pub struct Grid {
     points: Vec<GridPoint>,
     boundary_coord: Coordinate,
 }
@@ -60,8 +62,8 @@ impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "\n-----")?;
         for (index, point) in self.points.iter().enumerate() {
-            if index as u32 % (self.boundary_coord.x + 1) == 0 {
-                write!(f, "\n")?;
+            if (index as u32).is_multiple_of(self.boundary_coord.x + 1) {
+                writeln!(f)?;
             }
             match point {
                 GridPoint::Unfilled { x: _, y: _ } => { write!(f, "-")?; },
@@ -82,79 +84,60 @@ impl fmt::Display for Grid {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct MalformedCoordinate {
-    details: String
-}
-
-impl MalformedCoordinate {
-    fn new(msg: &str) -> MalformedCoordinate {
-        MalformedCoordinate{ details: msg.to_string() }
-    }
-}
-
-impl fmt::Display for MalformedCoordinate {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
-    }
+pub fn solve_part1(input: &Input) -> Result<u32> {
+    largest_finite_area(&read_coordinates(input)?)
 }
 
-impl Error for MalformedCoordinate {
-    fn description(&self) -> &str {
-        &self.details
-    }
+pub fn solve_part2(input: &Input) -> Result<u32> {
+    Ok(safe_region_size(&read_coordinates(input)?))
 }
 
-pub fn solve_part1() -> Result<u32, Box<Error>> {
-    let coords = read_coordinates(INPUT)?;
-    let boundary_coord = get_boundary_coordinate(&coords);
+pub fn largest_finite_area(coords: &[Coordinate]) -> Result<u32> {
+    let boundary_coord = get_boundary_coordinate(coords);
     let mut grid = create_grid(boundary_coord);
-    fill_grid(&mut grid, &coords).unwrap();
-    println!("{}", grid);
+    fill_grid(&mut grid, coords)?;
     Ok(find_largest_coord_area(grid))
 }
 
-pub fn solve_part2() -> Result<u32, Box<Error>> {
-    let coords = read_coordinates(INPUT)?;
-    let boundary_coord = get_boundary_coordinate(&coords);
+pub fn safe_region_size(coords: &[Coordinate]) -> u32 {
+    let boundary_coord = get_boundary_coordinate(coords);
     let grid = create_grid(boundary_coord);
-    Ok(region_closest_to_coordinates_size(grid, coords))
+    region_closest_to_coordinates_size(grid, coords)
 }
 
-fn read_coordinates(filename: &str) -> Result<Vec<Coordinate>, Box<Error>> {
+pub fn read_coordinates(input: &Input) -> Result<Vec<Coordinate>> {
     let mut records: Vec<Coordinate> = Vec::new();
     lazy_static! {
         static ref COORDINATE_REGEX: Regex = Regex::new(
             r"(?P<x>\d+), (?P<y>\d+)").unwrap();
     }
-    let file = File::open(filename)?;
-    for (index, line) in BufReader::new(file).lines().enumerate() {
-        match COORDINATE_REGEX.captures(&line?) {
+    for (index, line) in input.reader()?.lines().enumerate() {
+        let line = line?;
+        let letter = match ALPHABET.get(index) {
+            Some(&letter) => letter,
+            None => return Err(Error::parse(
+                index + 1,
+                &line,
+                format!("Too many coordinates, only {} can be labelled", ALPHABET.len()),
+            )),
+        };
+        match COORDINATE_REGEX.captures(&line) {
             Some(captures) => {
-                records.push(Coordinate {
-                    x: get_captured_field(&captures, "x")?.parse()?,
-                    y: get_captured_field(&captures, "y")?.parse()?,
-                    letter: ALPHABET[index],
-                });
+                let x = parse_field(&captures, "x", &line).map_err(|err| err.at_line(index + 1))?;
+                let y = parse_field(&captures, "y", &line).map_err(|err| err.at_line(index + 1))?;
+                records.push(Coordinate { x, y, letter });
             },
-            None => return Err(Box::new(MalformedCoordinate {
-                details: "Malformed coordinate line, no fields could be found".to_string()
-            })),
+            None => return Err(Error::parse(
+                index + 1,
+                &line,
+                "Malformed coordinate line, no fields could be found",
+            )),
         };
     }
     Ok(records)
 }
 
-fn get_captured_field(captures: &Captures, field: &str) -> Result<String, Box<Error>> {
-    match captures.name(field) {
-        Some(capture) => Ok(String::from(capture.as_str())),
-        None => return Err(Box::new(MalformedCoordinate {
-            details: format!("Malformed coordinate line, field {} could not be found", field)
-        }))
-    }
-}
-
-fn get_boundary_coordinate(coords: &Vec<Coordinate>) -> Coordinate {
+pub fn get_boundary_coordinate(coords: &[Coordinate]) -> Coordinate {
     let mut boundary_coord = Coordinate { x: 0, y: 0, letter: '+' };
     for coord in coords {
         if coord.x > boundary_coord.x {
@@ -167,7 +150,7 @@ fn get_boundary_coordinate(coords: &Vec<Coordinate>) -> Coordinate {
     boundary_coord
 }
 
-fn create_grid(boundary_coord: Coordinate) -> Grid {
+pub fn create_grid(boundary_coord: Coordinate) -> Grid {
     let mut points = Vec::new();
     for y in 0..boundary_coord.y + 1 {
         for x in 0..boundary_coord.x + 1 {
@@ -177,10 +160,10 @@ fn create_grid(boundary_coord: Coordinate) -> Grid {
     Grid { points, boundary_coord }
 }
 
-fn fill_grid<'a>(
+pub fn fill_grid<'a>(
     grid: &'a mut Grid,
-    coords: &'a Vec<Coordinate>,
-) -> Result<&'a mut Grid, Box<Error>> {
+    coords: &'a [Coordinate],
+) -> Result<&'a mut Grid> {
     for coord in coords {
         let start_index = (coord.x * (grid.boundary_coord.y + 1)) + coord.y;
         fill_grid_with_coordinate(
@@ -196,15 +179,15 @@ fn fill_grid_with_coordinate(
     grid: &mut Grid,
     index: u32,
     coord: Coordinate,
-) -> Result<&mut Grid, Box<Error>> {
+) -> Result<&mut Grid> {
     let mut visited_indices = HashSet::new();
     for point in &mut grid.points {
         visited_indices.insert(index);
         match *point {
             GridPoint::Unfilled { x, y } => {
                 *point = GridPoint::Filled {
-                    x: x,
-                    y: y,
+                    x,
+                    y,
                     closest_coord: coord,
                     closest_dist: manhattan_dist(coord.x, coord.y, x, y),
                 };
@@ -213,8 +196,8 @@ fn fill_grid_with_coordinate(
                 let dist = manhattan_dist(coord.x, coord.y, x, y);
                 if dist < closest_dist {
                     *point = GridPoint::Filled {
-                        x: x,
-                        y: y,
+                        x,
+                        y,
                         closest_coord: coord,
                         closest_dist: dist,
                     };
@@ -224,15 +207,15 @@ fn fill_grid_with_coordinate(
                 let dist = manhattan_dist(coord.x, coord.y, x, y);
                 if dist < closest_dist {
                     *point = GridPoint::Filled {
-                        x: x,
-                        y: y,
+                        x,
+                        y,
                         closest_coord: coord,
                         closest_dist: dist,
                     };
                 } else if dist == closest_dist && closest_coord != coord {
                     *point = GridPoint::Tied {
-                        x: x,
-                        y: y,
+                        x,
+                        y,
                         closest_dist: dist,
                     };
                 }
@@ -246,32 +229,29 @@ fn manhattan_dist(x1: u32, y1: u32, x2: u32, y2: u32) -> u32 {
     ((x2 as i32 - x1 as i32).abs() + (y2 as i32 - y1 as i32).abs()) as u32
 }
 
-fn find_largest_coord_area(
+pub fn find_largest_coord_area(
     grid: Grid,
 ) -> u32 {
     let mut point_count = HashMap::new();
     let mut infinite_coords = HashSet::new();
     for point in grid.points.iter() {
-        match point {
-            GridPoint::Filled { x, y, closest_coord: coord, closest_dist: _ } => {
-                if *x == 0 || *x == grid.boundary_coord.x ||
-                    *y == 0 || *y == grid.boundary_coord.y {
-                    point_count.remove(coord);
-                    infinite_coords.insert(coord);
-                    continue;
-                }
-                if !infinite_coords.contains(coord) {
-                    let count = point_count.entry(coord).or_insert(0);
-                    *count += 1;
-                }
-            },
-            _ => ()
+        if let GridPoint::Filled { x, y, closest_coord: coord, closest_dist: _ } = point {
+            if *x == 0 || *x == grid.boundary_coord.x ||
+                *y == 0 || *y == grid.boundary_coord.y {
+                point_count.remove(coord);
+                infinite_coords.insert(coord);
+                continue;
+            }
+            if !infinite_coords.contains(coord) {
+                let count = point_count.entry(coord).or_insert(0);
+                *count += 1;
+            }
         }
     }
     *point_count.values().max().unwrap_or(&0)
 }
 
-fn region_closest_to_coordinates_size(grid: Grid, coords: Vec<Coordinate>) -> u32 {
+pub fn region_closest_to_coordinates_size(grid: Grid, coords: &[Coordinate]) -> u32 {
     let mut points_in_region = 0;
     for point in grid.points.iter() {
         match point {
@@ -291,6 +271,24 @@ fn region_closest_to_coordinates_size(grid: Grid, coords: Vec<Coordinate>) -> u3
     points_in_region
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Vec<Coordinate>;
+
+    fn parse(input: &Input) -> Result<Vec<Coordinate>> {
+        read_coordinates(input)
+    }
+
+    fn part1(coords: &Vec<Coordinate>) -> Result<Answer> {
+        Ok(largest_finite_area(coords)?.into())
+    }
+
+    fn part2(coords: &Vec<Coordinate>) -> Result<Answer> {
+        Ok(safe_region_size(coords).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,7 +297,7 @@ mod tests {
 
     #[test]
     fn read_coordinates_file() {
-        assert_eq!(read_coordinates(TEST_INPUT).unwrap(), vec![
+        assert_eq!(read_coordinates(&Input::file(TEST_INPUT)).unwrap(), vec![
             Coordinate {
                 x: 1,
                 y: 1,
@@ -335,7 +333,7 @@ mod tests {
 
     #[test]
     fn gets_boundary_coordinate() {
-        assert_eq!(get_boundary_coordinate(&vec![
+        assert_eq!(get_boundary_coordinate(&[
             Coordinate {
                 x: 1,
                 y: 1,
@@ -401,7 +399,7 @@ mod tests {
         let mut grid = create_grid(boundary_coord);
         let coord = Coordinate { x: 0, y: 0, letter: 'a' };
         assert_eq!(
-            fill_grid(&mut grid, &vec![coord]).unwrap(),
+            fill_grid(&mut grid, &[coord]).unwrap(),
             &mut Grid {
                 points: vec![
                     GridPoint::Filled {
@@ -441,7 +439,7 @@ mod tests {
         let coord_a = Coordinate { x: 0, y: 0, letter: 'a' };
         let coord_b = Coordinate { x: 1, y: 1, letter: 'b' };
         assert_eq!(
-            fill_grid(&mut grid, &vec![coord_a, coord_b]).unwrap(),
+            fill_grid(&mut grid, &[coord_a, coord_b]).unwrap(),
             &mut Grid {
                 points: vec![
                     GridPoint::Filled {
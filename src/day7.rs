@@ -1,36 +1,35 @@
 extern crate regex;
 
 use std::collections::HashMap;
-use std::error::Error;
 use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::result;
+use std::io::BufRead;
 
-use regex::{Captures, Regex};
+use regex::Regex;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-type Instructions = HashMap<char, Vec<char>>;
+pub type Instructions = HashMap<char, Vec<char>>;
 
-const INPUT: &str = "inputs/7.txt";
+pub const INPUT: &str = "inputs/7.txt";
 static ALPHABET: [char; 26] = [
     'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
     'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
 
-pub fn solve_part1() -> Result<String> {
-    let mut instructions = read_instructions(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<String> {
+    let mut instructions = read_instructions(input)?;
     Ok(get_step_sequence(&mut instructions))
 }
 
-pub fn solve_part2() -> Result<u32> {
+pub fn solve_part2(input: &Input) -> Result<u32> {
     let mut pool = WorkerPool::new(5);
-    let mut instructions = read_instructions(INPUT)?;
+    let mut instructions = read_instructions(input)?;
     Ok(get_parallel_step_sequence_seconds(&mut instructions, &mut pool))
 }
 
-fn read_instructions(filename: &str) -> Result<Instructions> {
+pub fn read_instructions(input: &Input) -> Result<Instructions> {
     let mut instructions: Instructions = HashMap::new();
     lazy_static! {
         static ref INSTRUCTION_REGEX: Regex = Regex::new(
@@ -38,18 +37,22 @@ fn read_instructions(filename: &str) -> Result<Instructions> {
         )
         .unwrap();
     }
-    let file = File::open(filename)?;
-    for line in BufReader::new(file).lines() {
-        match INSTRUCTION_REGEX.captures(&line?) {
+    for (index, line) in input.reader()?.lines().enumerate() {
+        let line = line?;
+        match INSTRUCTION_REGEX.captures(&line) {
             Some(captures) => {
-                let step = get_captured_field(&captures, "step")?;
-                let dependency: char = get_captured_field(&captures, "dependency")?;
-                instructions.entry(dependency).or_insert_with(Vec::new);
-                let dependencies = instructions.entry(step).or_insert_with(Vec::new);
+                let step: char = parse_field(&captures, "step", &line)
+                    .map_err(|err| err.at_line(index + 1))?;
+                let dependency: char = parse_field(&captures, "dependency", &line)
+                    .map_err(|err| err.at_line(index + 1))?;
+                instructions.entry(dependency).or_default();
+                let dependencies = instructions.entry(step).or_default();
                 dependencies.push(dependency);
             }
             None => {
-                return Err(From::from(
+                return Err(Error::parse(
+                    index + 1,
+                    &line,
                     "Malformed instruction line, no fields could be found",
                 ))
             }
@@ -58,23 +61,7 @@ fn read_instructions(filename: &str) -> Result<Instructions> {
     Ok(instructions)
 }
 
-fn get_captured_field(captures: &Captures, field: &str) -> Result<char> {
-    match captures.name(field) {
-        Some(capture) => match capture.as_str().chars().next() {
-            Some(letter) => Ok(letter),
-            None => Err(From::from(format!(
-                "Malformed instruction line, field {} not a char",
-                field
-            ))),
-        },
-        None => Err(From::from(format!(
-            "Malformed instruction line, field {} could not be found",
-            field
-        ))),
-    }
-}
-
-fn get_step_sequence(instructions: &mut Instructions) -> String {
+pub fn get_step_sequence(instructions: &mut Instructions) -> String {
     let mut sequence = String::new();
     loop {
         let mut available: Vec<char> = instructions
@@ -99,14 +86,14 @@ fn get_step_sequence(instructions: &mut Instructions) -> String {
     sequence
 }
 
-fn get_parallel_step_sequence_seconds(
-    mut instructions: &mut Instructions,
+pub fn get_parallel_step_sequence_seconds(
+    instructions: &mut Instructions,
     worker_pool: &mut WorkerPool,
 ) -> u32 {
     let mut sequence = String::new();
     let mut seconds = 0;
     loop {
-        worker_pool.run_one_second(&mut instructions, &mut sequence);
+        worker_pool.run_one_second(instructions, &mut sequence);
 
         let mut available: Vec<char> = instructions
             .iter()
@@ -121,7 +108,7 @@ fn get_parallel_step_sequence_seconds(
         available.sort();
         available.reverse();
 
-        for mut worker in worker_pool.available() {
+        for worker in worker_pool.available() {
             let next = match available.pop() {
                 None => break,
                 Some(next) => next,
@@ -131,33 +118,32 @@ fn get_parallel_step_sequence_seconds(
         }
         seconds += 1;
     }
-    println!("{}", sequence);
     seconds
 }
 
 fn get_seconds_for_step(step_letter: char) -> u8 {
-    ALPHABET.iter().position(|&c| c == step_letter).unwrap_or(0) as u8 + 61 as u8
+    ALPHABET.iter().position(|&c| c == step_letter).unwrap_or(0) as u8 + 61_u8
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct Worker {
+pub struct Worker {
     id: u8,
     status: Status,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Status {
+pub enum Status {
     Idle,
     Working { step: char, remaining: u8 },
 }
 
 #[derive(Debug, PartialEq)]
-struct WorkerPool {
+pub struct WorkerPool {
     workers: Vec<Worker>,
 }
 
 impl WorkerPool {
-    fn new(count: u8) -> WorkerPool {
+    pub fn new(count: u8) -> WorkerPool {
         let mut workers = Vec::new();
         for i in 0..count {
             workers.push(Worker {
@@ -168,7 +154,7 @@ impl WorkerPool {
         WorkerPool { workers }
     }
 
-    fn available(&self) -> Vec<Worker> {
+    pub fn available(&self) -> Vec<Worker> {
         self.workers
             .iter()
             .filter(|worker| match worker.status {
@@ -179,13 +165,13 @@ impl WorkerPool {
             .collect()
     }
 
-    fn all_idle(&self) -> bool {
+    pub fn all_idle(&self) -> bool {
         self.workers
             .iter()
             .all(|worker| worker.status == Status::Idle)
     }
 
-    fn run_one_second(&mut self, instructions: &mut Instructions, sequence: &mut String) {
+    pub fn run_one_second(&mut self, instructions: &mut Instructions, sequence: &mut String) {
         let new_workers = self
             .workers
             .iter()
@@ -215,7 +201,7 @@ impl WorkerPool {
         self.workers = new_workers;
     }
 
-    fn assign_worker(&mut self, id: u8, step: char) {
+    pub fn assign_worker(&mut self, id: u8, step: char) {
         let new_workers = self
             .workers
             .iter()
@@ -257,6 +243,25 @@ impl fmt::Display for Worker {
     }
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Instructions;
+
+    fn parse(input: &Input) -> Result<Instructions> {
+        read_instructions(input)
+    }
+
+    fn part1(instructions: &Instructions) -> Result<Answer> {
+        Ok(get_step_sequence(&mut instructions.clone()).into())
+    }
+
+    fn part2(instructions: &Instructions) -> Result<Answer> {
+        let mut pool = WorkerPool::new(5);
+        Ok(get_parallel_step_sequence_seconds(&mut instructions.clone(), &mut pool).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,7 +284,7 @@ mod tests {
 
     #[test]
     fn reads_instructions_file() {
-        assert_eq!(read_instructions(TEST_INPUT).unwrap(), test_instructions());
+        assert_eq!(read_instructions(&Input::file(TEST_INPUT)).unwrap(), test_instructions());
     }
 
     #[test]
@@ -421,13 +426,13 @@ mod tests {
 
     #[test]
     fn worker_pool_all_idle() {
-        assert_eq!(WorkerPool::new(3).all_idle(), true);
+        assert!(WorkerPool::new(3).all_idle());
     }
 
     #[test]
     fn worker_pool_not_all_idle() {
-        assert_eq!(
-            WorkerPool {
+        assert!(
+            !WorkerPool {
                 workers: vec![
                     Worker {
                         id: 0,
@@ -442,8 +447,7 @@ mod tests {
                     },
                 ],
             }
-            .all_idle(),
-            false
+            .all_idle()
         );
     }
 
@@ -1,28 +1,32 @@
-use std::error::Error;
-use std::fs;
-use std::result;
+use crate::error::{ParseContext, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-type Result<T> = result::Result<T, Box<Error>>;
+pub const INPUT: &str = "inputs/8.txt";
 
-const INPUT: &str = "inputs/8.txt";
-
-pub fn solve_part1() -> Result<u32> {
-    let license = read_license(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<u32> {
+    let license = read_license(input)?;
     Ok(sum_metadata(&license, 0, 0).0)
 }
 
-pub fn solve_part2() -> Result<u32> {
-    let license = read_license(INPUT)?;
+pub fn solve_part2(input: &Input) -> Result<u32> {
+    let license = read_license(input)?;
     Ok(sum_metadata_with_indices(&license, 0).0)
 }
 
-fn read_license(filename: &str) -> Result<Vec<u32>> {
-    let license = fs::read_to_string(filename)?;
+pub fn read_license(input: &Input) -> Result<Vec<u32>> {
+    let license = input.read_to_string()?;
     let license = license.trim();
-    Ok(license.split(' ').map(|num| num.parse().unwrap()).collect())
+    let mut numbers = Vec::new();
+    let mut column = 1;
+    for num in license.split(' ') {
+        numbers.push(num.parse().on_line(1, license).map_err(|err| err.at_column(column))?);
+        column += num.len() + 1;
+    }
+    Ok(numbers)
 }
 
-fn sum_metadata(license: &[u32], mut index: usize, mut sum_acc: u32) -> (u32, usize) {
+pub fn sum_metadata(license: &[u32], mut index: usize, mut sum_acc: u32) -> (u32, usize) {
     let num_children = license[index];
     let num_metadata = license[index + 1];
     index += 2;
@@ -41,7 +45,7 @@ fn sum_metadata(license: &[u32], mut index: usize, mut sum_acc: u32) -> (u32, us
     (sum_acc, index)
 }
 
-fn sum_metadata_with_indices(license: &[u32], mut index: usize) -> (u32, usize) {
+pub fn sum_metadata_with_indices(license: &[u32], mut index: usize) -> (u32, usize) {
     let mut sum: u32 = 0;
     let num_children = license[index];
     let num_metadata = license[index + 1];
@@ -68,6 +72,24 @@ fn sum_metadata_with_indices(license: &[u32], mut index: usize) -> (u32, usize)
     (sum, index)
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Vec<u32>;
+
+    fn parse(input: &Input) -> Result<Vec<u32>> {
+        read_license(input)
+    }
+
+    fn part1(license: &Vec<u32>) -> Result<Answer> {
+        Ok(sum_metadata(license, 0, 0).0.into())
+    }
+
+    fn part2(license: &Vec<u32>) -> Result<Answer> {
+        Ok(sum_metadata_with_indices(license, 0).0.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,7 +101,18 @@ mod tests {
 
     #[test]
     fn reads_license_file() {
-        assert_eq!(read_license(TEST_INPUT).unwrap(), test_license());
+        assert_eq!(read_license(&Input::file(TEST_INPUT)).unwrap(), test_license());
+    }
+
+    #[test]
+    fn errors_on_malformed_license() {
+        match read_license(&Input::text("2 3 0 x 10")) {
+            Ok(_) => panic!("read_license should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 1, column 7: invalid digit found in string: \"2 3 0 x 10\""
+            ),
+        }
     }
 
     #[test]
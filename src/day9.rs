@@ -1,26 +1,25 @@
 extern crate regex;
 
-use std::error::Error;
 use std::fmt;
-use std::fs;
-use std::result;
 use std::str::FromStr;
 use std::collections::VecDeque;
 
 use regex::Regex;
 
-type Result<T> = result::Result<T, Box<Error>>;
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
 
-const INPUT: &str = "inputs/9.txt";
+pub const INPUT: &str = "inputs/9.txt";
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct GameParameters {
+pub struct GameParameters {
     players: usize,
     last_marble: usize,
 }
 
 impl FromStr for GameParameters {
-    type Err = Box<Error>;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<GameParameters> {
         lazy_static! {
@@ -30,23 +29,26 @@ impl FromStr for GameParameters {
             .unwrap();
         }
 
+        let s = s.trim_end();
         let captures = match RE.captures(s) {
             None => {
-                return Err(From::from(
+                return Err(Error::parse(
+                    1,
+                    s,
                     "Malformed game parameters, no fields could be found",
                 ));
             }
             Some(captures) => captures,
         };
         Ok(GameParameters {
-            players: captures["players"].parse()?,
-            last_marble: captures["last_marble"].parse()?,
+            players: parse_field(&captures, "players", s)?,
+            last_marble: parse_field(&captures, "last_marble", s)?,
         })
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct GameState {
+pub struct GameState {
     turn: Option<usize>,
     circle: VecDeque<usize>,
     current_marble: usize,
@@ -54,12 +56,12 @@ struct GameState {
 }
 
 impl GameState {
-    fn new(parameters: GameParameters) -> GameState {
+    pub fn new(parameters: GameParameters) -> GameState {
         GameState {
             turn: None,
             circle: VecDeque::from(vec![0]),
             current_marble: 0,
-            player_scores: vec![0; parameters.players as usize],
+            player_scores: vec![0; parameters.players],
         }
     }
 
@@ -79,14 +81,14 @@ impl GameState {
         }
     }
 
-    fn play_until_marble(&mut self, last_marble: usize) {
+    pub fn play_until_marble(&mut self, last_marble: usize) {
         for _ in 0..last_marble {
             self.turn = match self.turn {
                 None => Some(0),
                 Some(turn) => Some((turn + 1) % self.player_scores.len()),
             };
 
-            if (self.current_marble + 1) % 23 == 0 {
+            if (self.current_marble + 1).is_multiple_of(23) {
                 self.place_23rd_marble();
             } else {
                 self.place_next_marble();
@@ -111,7 +113,7 @@ impl GameState {
         self.rotate_counter_clockwise(1);
     }
 
-    fn highest_score(&mut self) -> usize {
+    pub fn highest_score(&mut self) -> usize {
         *self.player_scores.iter().max().unwrap_or(&0)
     }
 }
@@ -133,28 +135,48 @@ impl fmt::Display for GameState {
     }
 }
 
-pub fn solve_part1() -> Result<usize> {
-    let game_params = read_game_parameters(INPUT)?;
+pub fn solve_part1(input: &Input) -> Result<usize> {
+    let game_params = read_game_parameters(input)?;
     Ok(get_highest_score_for_game(game_params))
 }
 
-pub fn solve_part2() -> Result<usize> {
-    let mut game_params = read_game_parameters(INPUT)?;
+pub fn solve_part2(input: &Input) -> Result<usize> {
+    let mut game_params = read_game_parameters(input)?;
     game_params.last_marble *= 100;
     Ok(get_highest_score_for_game(game_params))
 }
 
-fn read_game_parameters(filename: &str) -> Result<GameParameters> {
-    let game_params = fs::read_to_string(filename)?;
-    Ok(game_params.parse()?)
+pub fn read_game_parameters(input: &Input) -> Result<GameParameters> {
+    let game_params = input.read_to_string()?;
+    game_params.parse()
 }
 
-fn get_highest_score_for_game(game_params: GameParameters) -> usize {
+pub fn get_highest_score_for_game(game_params: GameParameters) -> usize {
     let mut game_state = GameState::new(game_params);
     game_state.play_until_marble(game_params.last_marble);
     game_state.highest_score()
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = GameParameters;
+
+    fn parse(input: &Input) -> Result<GameParameters> {
+        read_game_parameters(input)
+    }
+
+    fn part1(game_params: &GameParameters) -> Result<Answer> {
+        Ok(get_highest_score_for_game(*game_params).into())
+    }
+
+    fn part2(game_params: &GameParameters) -> Result<Answer> {
+        let mut game_params = *game_params;
+        game_params.last_marble *= 100;
+        Ok(get_highest_score_for_game(game_params).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,7 +189,7 @@ mod tests {
 
     #[test]
     fn reads_game_parameters_file() {
-        assert_eq!(read_game_parameters(TEST_INPUT).unwrap(), TEST_GAME_PARAMS);
+        assert_eq!(read_game_parameters(&Input::file(TEST_INPUT)).unwrap(), TEST_GAME_PARAMS);
     }
 
     #[test]
@@ -0,0 +1,189 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+use std::str::FromStr;
+
+use regex::Captures;
+
+use crate::input::Input;
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io(io::Error),
+    /// A line of the input is not in the format the day expects.
+    Parse {
+        input: Option<String>,
+        line: usize,
+        column: Option<usize>,
+        text: String,
+        message: String,
+    },
+    /// Anything that goes wrong after the input has been parsed.
+    Simulation(String),
+}
+
+impl Error {
+    pub fn parse<S: Into<String>>(line: usize, text: &str, message: S) -> Error {
+        Error::Parse {
+            input: None,
+            line,
+            column: None,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn simulation<S: Into<String>>(message: S) -> Error {
+        Error::Simulation(message.into())
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io(_) => "io",
+            Error::Parse { .. } => "parse",
+            Error::Simulation(_) => "simulation",
+        }
+    }
+
+    /// Moves a parse error to `line`, for parsers that work on one line at a time and report
+    /// their errors at line 1.
+    pub fn at_line(mut self, line_number: usize) -> Error {
+        if let Error::Parse { ref mut line, .. } = self {
+            *line = line_number;
+        }
+        self
+    }
+
+    pub fn at_column(mut self, column_number: usize) -> Error {
+        if let Error::Parse { ref mut column, .. } = self {
+            *column = Some(column_number);
+        }
+        self
+    }
+
+    /// Names the input a parse error came from, unless it has already been named.
+    pub fn in_input(mut self, from: &Input) -> Error {
+        if let Error::Parse { ref mut input, .. } = self {
+            if input.is_none() {
+                *input = Some(from.to_string());
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse {
+                input,
+                line,
+                column,
+                text,
+                message,
+            } => {
+                match (input, column) {
+                    (Some(input), Some(column)) => write!(f, "{}:{}:{}", input, line, column)?,
+                    (Some(input), None) => write!(f, "{}:{}", input, line)?,
+                    (None, Some(column)) => write!(f, "line {}, column {}", line, column)?,
+                    (None, None) => write!(f, "line {}", line)?,
+                }
+                write!(f, ": {}: {:?}", message, text)
+            }
+            Error::Simulation(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+/// Turns the error of a `str::parse` (or anything else that fails with a displayable error) into
+/// a parse error on the given line of input.
+pub trait ParseContext<T> {
+    fn on_line(self, line: usize, text: &str) -> Result<T>;
+}
+
+impl<T, E: fmt::Display> ParseContext<T> for result::Result<T, E> {
+    fn on_line(self, line: usize, text: &str) -> Result<T> {
+        self.map_err(|err| Error::parse(line, text, err.to_string()))
+    }
+}
+
+/// Parses the named capture of a regex matched against a single line of input, reporting the
+/// column the capture started at if it fails.
+pub fn parse_field<T>(captures: &Captures, field: &str, text: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    match captures.name(field) {
+        Some(capture) => capture
+            .as_str()
+            .trim_start()
+            .parse()
+            .on_line(1, text)
+            .map_err(|err| err.at_column(capture.start() + 1)),
+        None => Err(Error::parse(
+            1,
+            text,
+            format!("Malformed line, field {} could not be found", field),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use regex::Regex;
+
+    #[test]
+    fn displays_parse_errors_with_their_location() {
+        let err = Error::parse(1, "#1 @ 1,3: 4x", "Malformed claim line");
+        assert_eq!(err.to_string(), "line 1: Malformed claim line: \"#1 @ 1,3: 4x\"");
+        let err = err.at_line(7).at_column(4).in_input(&Input::file("inputs/3.txt"));
+        assert_eq!(
+            err.to_string(),
+            "inputs/3.txt:7:4: Malformed claim line: \"#1 @ 1,3: 4x\""
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_input_named() {
+        let err = Error::parse(2, "x", "bad")
+            .in_input(&Input::file("inputs/1.txt"))
+            .in_input(&Input::Stdin);
+        assert_eq!(err.to_string(), "inputs/1.txt:2: bad: \"x\"");
+    }
+
+    #[test]
+    fn parses_fields_with_their_column() {
+        let re = Regex::new(r"(?P<x>\d+), (?P<y>\d+)").unwrap();
+        let text = "1, 99999999999";
+        let captures = re.captures(text).unwrap();
+        assert_eq!(parse_field::<u32>(&captures, "x", text).unwrap(), 1);
+        match parse_field::<u32>(&captures, "y", text) {
+            Err(Error::Parse { line, column, .. }) => assert_eq!((line, column), (1, Some(4))),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+        assert!(parse_field::<u32>(&captures, "z", text).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Input {
+    File(PathBuf),
+    Text(String),
+    Stdin,
+    /// Text that was read from another input, which is still named after it.
+    Buffered { name: String, text: String },
+}
+
+impl Input {
+    pub fn file<P: Into<PathBuf>>(path: P) -> Input {
+        Input::File(path.into())
+    }
+
+    pub fn text<S: Into<String>>(text: S) -> Input {
+        Input::Text(text.into())
+    }
+
+    /// Parses a command-line input argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::file(path),
+        }
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        match self {
+            Input::File(path) => fs::read_to_string(path),
+            Input::Text(text) | Input::Buffered { text, .. } => Ok(text.clone()),
+            Input::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Ok(text)
+            }
+        }
+    }
+
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Input::File(path) => Box::new(BufReader::new(File::open(path)?)),
+            Input::Text(text) | Input::Buffered { text, .. } => {
+                Box::new(Cursor::new(text.clone().into_bytes()))
+            }
+            Input::Stdin => Box::new(BufReader::new(io::stdin())),
+        })
+    }
+
+    /// 64-bit FNV-1a hash of the input's contents, to tell which input an answer came from.
+    pub fn fnv1a_hash(&self) -> io::Result<u64> {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in self.read_to_string()?.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(hash)
+    }
+
+    /// Reads stdin into memory so that the input can be read more than once, keeping its name
+    /// for error messages.
+    pub fn buffered(self) -> io::Result<Input> {
+        match self {
+            Input::Stdin => Ok(Input::Buffered {
+                name: Input::Stdin.to_string(),
+                text: Input::Stdin.read_to_string()?,
+            }),
+            input => Ok(input),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Text(_) => write!(f, "<text>"),
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::Buffered { name, .. } => write!(f, "{}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "inputs/1_test.txt";
+
+    #[test]
+    fn parses_input_args() {
+        assert_eq!(Input::from_arg("-"), Input::Stdin);
+        assert_eq!(Input::from_arg(TEST_INPUT), Input::file(TEST_INPUT));
+    }
+
+    #[test]
+    fn reads_file_to_string() {
+        assert_eq!(Input::file(TEST_INPUT).read_to_string().unwrap(), "+5\n-5\n+3\n");
+    }
+
+    #[test]
+    fn reads_text_lines() {
+        let lines: Vec<String> = Input::text("+5\n-5\n+3\n")
+            .reader()
+            .unwrap()
+            .lines()
+            .map(|line| line.unwrap())
+            .collect();
+        assert_eq!(lines, vec!["+5", "-5", "+3"]);
+    }
+
+    #[test]
+    fn errors_on_missing_file() {
+        assert!(Input::file("inputs/missing.txt").read_to_string().is_err());
+    }
+
+    #[test]
+    fn hashes_input_contents() {
+        assert_eq!(Input::text("").fnv1a_hash().unwrap(), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(Input::text("a").fnv1a_hash().unwrap(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn displays_input_name() {
+        assert_eq!(Input::file(TEST_INPUT).to_string(), TEST_INPUT);
+        assert_eq!(Input::Stdin.to_string(), "<stdin>");
+        let buffered = Input::Buffered {
+            name: Input::Stdin.to_string(),
+            text: "+1\n".to_string(),
+        };
+        assert_eq!(buffered.to_string(), "<stdin>");
+        assert_eq!(buffered.read_to_string().unwrap(), "+1\n");
+    }
+}
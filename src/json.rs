@@ -0,0 +1,193 @@
+use std::fmt;
+
+use crate::error::Error;
+use crate::solution::Answer;
+
+/// Just enough of JSON to write out results, without pulling in a serialization crate.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i128),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub fn string<S: Into<String>>(string: S) -> Json {
+        Json::String(string.into())
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, string: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(boolean) => write!(f, "{}", boolean),
+            Json::Number(number) => write!(f, "{}", number),
+            Json::String(string) => write_string(f, string),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Integer(integer) => Json::Number(i128::from(*integer)),
+            Answer::Text(text) => Json::string(text.as_str()),
+            Answer::Coordinate { x, y } => Json::object(vec![
+                ("x", Json::Number(*x as i128)),
+                ("y", Json::Number(*y as i128)),
+            ]),
+            Answer::Subsection { x, y, size } => Json::object(vec![
+                ("x", Json::Number(*x as i128)),
+                ("y", Json::Number(*y as i128)),
+                ("size", Json::Number(*size as i128)),
+            ]),
+            Answer::Message { lines, seconds } => Json::object(vec![
+                (
+                    "lines",
+                    Json::Array(lines.iter().map(|line| Json::string(line.as_str())).collect()),
+                ),
+                ("seconds", Json::Number(i128::from(*seconds))),
+            ]),
+            Answer::None => Json::Null,
+        }
+    }
+}
+
+impl From<&Error> for Json {
+    fn from(err: &Error) -> Json {
+        let mut fields = vec![
+            ("kind", Json::string(err.kind())),
+            ("message", Json::string(err.to_string())),
+        ];
+        if let Error::Parse {
+            input,
+            line,
+            column,
+            text,
+            ..
+        } = err
+        {
+            fields.push((
+                "input",
+                input.as_ref().map_or(Json::Null, |input| Json::string(input.as_str())),
+            ));
+            fields.push(("line", Json::Number(*line as i128)));
+            fields.push((
+                "column",
+                column.map_or(Json::Null, |column| Json::Number(column as i128)),
+            ));
+            fields.push(("text", Json::string(text.as_str())));
+        }
+        Json::object(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_json() {
+        let json = Json::object(vec![
+            ("day", Json::Number(10)),
+            ("ok", Json::Bool(true)),
+            ("error", Json::Null),
+            ("lines", Json::Array(vec![Json::string("#..#"), Json::string("say \"hi\"\\\n")])),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r##"{"day":10,"ok":true,"error":null,"lines":["#..#","say \"hi\"\\\n"]}"##
+        );
+    }
+
+    #[test]
+    fn escapes_control_characters() {
+        assert_eq!(Json::string("\t\u{1}").to_string(), r##""\t\u0001""##);
+    }
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(Json::from(&Answer::Integer(-3)).to_string(), "-3");
+        assert_eq!(Json::from(&Answer::None).to_string(), "null");
+        assert_eq!(
+            Json::from(&Answer::Subsection {
+                x: 90,
+                y: 269,
+                size: 16
+            })
+            .to_string(),
+            r##"{"x":90,"y":269,"size":16}"##
+        );
+        assert_eq!(
+            Json::from(&Answer::Message {
+                lines: vec!["#.".to_string(), ".#".to_string()],
+                seconds: 3,
+            })
+            .to_string(),
+            r##"{"lines":["#.",".#"],"seconds":3}"##
+        );
+    }
+
+    #[test]
+    fn converts_parse_errors() {
+        let err = Error::parse(2, "x", "bad").at_column(1);
+        assert_eq!(
+            Json::from(&err).to_string(),
+            r##"{"kind":"parse","message":"line 2, column 1: bad: \"x\"","input":null,"line":2,"column":1,"text":"x"}"##
+        );
+        assert_eq!(
+            Json::from(&Error::simulation("stuck")).to_string(),
+            r##"{"kind":"simulation","message":"stuck"}"##
+        );
+    }
+}
@@ -1,69 +1,375 @@
-#![warn(clippy)]
-
-#[macro_use]
-extern crate lazy_static;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
+#![warn(clippy::all)]
+
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use advent_of_code_2018::error;
+use advent_of_code_2018::input::Input;
+use advent_of_code_2018::json::Json;
+use advent_of_code_2018::parallel;
+use advent_of_code_2018::registry::{self, Day, Part, DAYS};
+use advent_of_code_2018::solution::Answer;
+use advent_of_code_2018::timing::{format_duration, Summary};
+use advent_of_code_2018::verify::{self, Outcome};
+
+mod cli;
+
+use cli::{Command, Format, Selection};
 
 fn main() {
-    // println!("Day 1:");
-    // println!("{}", day1::solve_part1().unwrap());
-    // println!("{}", day1::solve_part2().unwrap().unwrap());
-    // println!("Day 2:");
-    // println!("{}", day2::solve_part1().unwrap());
-    // println!("{}", day2::solve_part2().unwrap().unwrap());
-    // println!("Day 3:");
-    // println!("{}", day3::solve_part1().unwrap());
-    // println!("{}", day3::solve_part2().unwrap().unwrap());
-    // println!("Day 4:");
-    // println!("{}", day4::solve_part1().unwrap());
-    // println!("{}", day4::solve_part2().unwrap());
-    // println!("Day 5:");
-    // println!("{}", day5::solve_part1().unwrap());
-    // println!("{}", day5::solve_part2().unwrap());
-    // println!("Day 6:");
-    // println!("{}", day6::solve_part1().unwrap());
-    // println!("{}", day6::solve_part2().unwrap());
-    // println!("Day 7:");
-    // println!("{}", day7::solve_part1().unwrap());
-    // println!("{}", day7::solve_part2().unwrap());
-    // println!("Day 8:");
-    // println!("{}", day8::solve_part1().unwrap());
-    // println!("{}", day8::solve_part2().unwrap());
-    // println!("Day 9:");
-    // println!("{}", day9::solve_part1().unwrap());
-    // println!("{}", day9::solve_part2().unwrap());
-    // println!("Day 10:");
-    // let parts = day10::solve_parts().unwrap();
-    // println!("{}", parts.0);
-    // println!("{}", parts.1);
-    // println!("Day 11:");
-    // let part1 = day11::solve_part1().unwrap();
-    // println!("{},{}", part1.coord.x, part1.coord.y);
-    // let part2 = day11::solve_part2().unwrap();
-    // println!("{},{},{}", part2.coord.x, part2.coord.y, part2.size);
-    // println!("Day 12:");
-    // println!("{}", day12::solve_part1().unwrap());
-    // println!("{}", day12::solve_part2().unwrap());
-    // println!("Day 13");
-    // let part1 = day13::solve_part1().unwrap();
-    // println!("{},{}", part1.x, part1.y);
-    // println!("{},{}", part2.x, part2.y);
-    // let part2 = day13::solve_part2().unwrap();
-    println!("Day 14");
-    println!("{}", day14::solve_part1().unwrap());
-    println!("{}", day14::solve_part2().unwrap());
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = run(command) {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::List => list_days(),
+        Command::Verify { day, answers } => {
+            let answers = answers.unwrap_or_else(|| Input::file(verify::ANSWERS));
+            verify_answers(day, &answers)?;
+        }
+        Command::Run {
+            selection,
+            part,
+            input,
+            time,
+            format,
+            jobs,
+        } => {
+            let days: Vec<&Day> = match selection {
+                Selection::All => DAYS
+                    .iter()
+                    .filter(|day| {
+                        let found = Path::new(day.input).exists();
+                        if !found {
+                            eprintln!("Skipping day {}: {} not found", day.day, day.input);
+                        }
+                        found
+                    })
+                    .collect(),
+                Selection::Day(day) => vec![registry::find_day(day)
+                    .ok_or_else(|| format!("Day {} has not been solved yet", day))?],
+            };
+            let input = match input {
+                Some(input) => Some(input.buffered()?),
+                None => None,
+            };
+            let day_inputs: Vec<DayInput> = days
+                .into_iter()
+                .map(|day| {
+                    let day_input = input.clone().unwrap_or_else(|| Input::file(day.input));
+                    (day, day_input)
+                })
+                .collect();
+            let jobs = jobs.unwrap_or_else(parallel::default_jobs);
+            match (format, time) {
+                (Format::Json, time) => json_days(&day_inputs, part, time.unwrap_or(1), jobs)?,
+                (Format::Text, Some(repeat)) => time_days(&day_inputs, part, repeat, jobs)?,
+                (Format::Text, None) => run_days(&day_inputs, part, jobs)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+fn list_days() {
+    for day in DAYS.iter() {
+        println!("Day {}:", day.day);
+        for part in day.parts.iter() {
+            println!("    Part {}: {}", part.part, part.returns);
+        }
+    }
+}
+
+fn verify_answers(day: Option<u8>, answers: &Input) -> Result<(), Box<dyn Error>> {
+    let answers: Vec<_> = verify::read_answers(answers)
+        .map_err(|err| err.in_input(answers))?
+        .into_iter()
+        .filter(|expected| day.is_none() || day == Some(expected.day))
+        .collect();
+    let (mut passed, mut mismatched, mut failed) = (0, 0, 0);
+    for expected in answers.iter() {
+        print!("Day {} part {}: ", expected.day, expected.part);
+        match verify::verify(expected) {
+            Outcome::Pass => {
+                passed += 1;
+                println!("pass");
+            }
+            Outcome::Mismatch { actual } => {
+                mismatched += 1;
+                println!("mismatch");
+                for line in verify::diff(&expected.answer, &actual).lines() {
+                    println!("    {}", line);
+                }
+            }
+            Outcome::Fail(err) => {
+                failed += 1;
+                println!("fail: {}", err);
+            }
+        }
+    }
+    for (unlisted_day, part) in verify::unlisted(&answers) {
+        if day.is_none() || day == Some(unlisted_day) {
+            println!("Day {} part {}: no expected answer", unlisted_day, part);
+        }
+    }
+    println!("{} passed, {} mismatched, {} failed", passed, mismatched, failed);
+    if mismatched + failed > 0 {
+        return Err(From::from(format!(
+            "{} of {} answers did not verify",
+            mismatched + failed,
+            answers.len()
+        )));
+    }
+    Ok(())
+}
+
+/// A day to run along with the input to run it on.
+type DayInput = (&'static Day, Input);
+
+fn selected_parts(day: &Day, part: Option<u8>) -> Vec<&Part> {
+    match part {
+        Some(part) => day.part(part).into_iter().collect(),
+        None => day.parts.iter().collect(),
+    }
+}
+
+/// Solves the selected parts of a day, stopping at the first part that fails.
+fn solve_day(day: &Day, part: Option<u8>, input: &Input) -> Vec<(u8, error::Result<Answer>)> {
+    let mut answers = Vec::new();
+    for day_part in selected_parts(day, part) {
+        let answer = day.solve(input, day_part.part);
+        let failed = answer.is_err();
+        answers.push((day_part.part, answer));
+        if failed {
+            break;
+        }
+    }
+    answers
+}
+
+fn run_days(day_inputs: &[DayInput], part: Option<u8>, jobs: usize) -> Result<(), Box<dyn Error>> {
+    parallel::run_ordered(
+        day_inputs,
+        jobs,
+        |(day, input)| (day.day, solve_day(day, part, input)),
+        |(day, answers)| {
+            println!("Day {}:", day);
+            for (part, answer) in answers {
+                let answer = answer?.to_string();
+                if answer.contains('\n') {
+                    println!("Part {}:\n{}", part, answer.trim_end());
+                } else {
+                    println!("Part {}: {}", part, answer);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+struct TimedPart {
+    day: u8,
+    part: u8,
+    answer: Answer,
+    parse: Summary,
+    solve: Summary,
+}
+
+fn time_part(day: &Day, part: u8, input: &Input, repeat: usize) -> error::Result<TimedPart> {
+    let mut answer = Answer::None;
+    let mut parse_times = Vec::new();
+    let mut solve_times = Vec::new();
+    for _ in 0..repeat {
+        let (part_answer, timing) = day.solve_timed(input, part)?;
+        answer = part_answer;
+        parse_times.push(timing.parse);
+        solve_times.push(timing.solve);
+    }
+    Ok(TimedPart {
+        day: day.day,
+        part,
+        answer,
+        parse: Summary::of(&parse_times),
+        solve: Summary::of(&solve_times),
+    })
+}
+
+fn time_days(
+    day_inputs: &[DayInput],
+    part: Option<u8>,
+    repeat: usize,
+    jobs: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut timed_parts = Vec::new();
+    parallel::run_ordered(
+        day_inputs,
+        jobs,
+        |(day, input)| {
+            selected_parts(day, part)
+                .iter()
+                .map(|day_part| time_part(day, day_part.part, input, repeat))
+                .collect::<error::Result<Vec<TimedPart>>>()
+        },
+        |day_timed_parts| {
+            timed_parts.extend(day_timed_parts?);
+            Ok::<(), error::Error>(())
+        },
+    )?;
+    print_timings(&timed_parts, repeat);
+    Ok(())
+}
+
+fn print_timings(timed_parts: &[TimedPart], repeat: usize) {
+    let format_summary = |summary: Summary| {
+        if repeat > 1 {
+            format!("{}/{}", format_duration(summary.min), format_duration(summary.median))
+        } else {
+            format_duration(summary.median)
+        }
+    };
+    let (parse_header, solve_header) = if repeat > 1 {
+        ("Parse min/median", "Solve min/median")
+    } else {
+        ("Parse", "Solve")
+    };
+    let total_median: Duration = timed_parts
+        .iter()
+        .map(|timed| timed.parse.median + timed.solve.median)
+        .sum();
+
+    println!(
+        "{:>3} {:>4}  {:<28} {:>17} {:>17} {:>6}",
+        "Day", "Part", "Answer", parse_header, solve_header, "Share"
+    );
+    for timed in timed_parts {
+        let answer = match &timed.answer {
+            Answer::Message { lines, .. } => format!("({}-line message)", lines.len()),
+            answer => answer.to_string(),
+        };
+        let share = if total_median > Duration::default() {
+            (timed.parse.median + timed.solve.median).as_secs_f64() / total_median.as_secs_f64()
+        } else {
+            0.0
+        };
+        println!(
+            "{:>3} {:>4}  {:<28} {:>17} {:>17} {:>5.1}%",
+            timed.day,
+            timed.part,
+            answer,
+            format_summary(timed.parse),
+            format_summary(timed.solve),
+            share * 100.0
+        );
+    }
+    let total = |select: fn(&TimedPart) -> Summary| Summary {
+        min: timed_parts.iter().map(|timed| select(timed).min).sum(),
+        median: timed_parts.iter().map(|timed| select(timed).median).sum(),
+    };
+    println!(
+        "{:<37} {:>17} {:>17} {:>6}",
+        format!("Total {}", format_duration(total_median)),
+        format_summary(total(|timed| timed.parse)),
+        format_summary(total(|timed| timed.solve)),
+        "100.0%"
+    );
+}
+
+fn json_record(
+    day: &Day,
+    part: u8,
+    input: &Input,
+    repeat: usize,
+    result: &error::Result<TimedPart>,
+) -> Json {
+    let input_hash = match input.fnv1a_hash() {
+        Ok(hash) => Json::string(format!("{:016x}", hash)),
+        Err(_) => Json::Null,
+    };
+    let mut fields = vec![
+        ("day", Json::Number(i128::from(day.day))),
+        ("part", Json::Number(i128::from(part))),
+        ("input", Json::string(input.to_string())),
+        ("input_fnv1a", input_hash),
+    ];
+    match result {
+        Ok(timed) => {
+            let nanos = |duration: Duration| Json::Number(duration.as_nanos() as i128);
+            fields.push(("answer_type", Json::string(timed.answer.kind())));
+            fields.push(("answer", Json::from(&timed.answer)));
+            fields.push((
+                "timing",
+                Json::object(vec![
+                    ("runs", Json::Number(repeat as i128)),
+                    ("parse_min_ns", nanos(timed.parse.min)),
+                    ("parse_median_ns", nanos(timed.parse.median)),
+                    ("solve_min_ns", nanos(timed.solve.min)),
+                    ("solve_median_ns", nanos(timed.solve.median)),
+                ]),
+            ));
+            fields.push(("error", Json::Null));
+        }
+        Err(err) => {
+            fields.push(("answer_type", Json::Null));
+            fields.push(("answer", Json::Null));
+            fields.push(("timing", Json::Null));
+            fields.push(("error", Json::from(err)));
+        }
+    }
+    Json::object(fields)
+}
+
+/// Prints a JSON array with a record per day and part. Failed parts are recorded with their error
+/// instead of stopping the run.
+fn json_days(
+    day_inputs: &[DayInput],
+    part: Option<u8>,
+    repeat: usize,
+    jobs: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut records = Vec::new();
+    let mut failed = 0;
+    parallel::run_ordered(
+        day_inputs,
+        jobs,
+        |(day, input)| {
+            selected_parts(day, part)
+                .iter()
+                .map(|day_part| {
+                    let result = time_part(day, day_part.part, input, repeat);
+                    (result.is_err(), json_record(day, day_part.part, input, repeat, &result))
+                })
+                .collect::<Vec<_>>()
+        },
+        |day_records| {
+            for (day_failed, record) in day_records {
+                if day_failed {
+                    failed += 1;
+                }
+                records.push(record);
+            }
+            Ok::<(), ()>(())
+        },
+    )
+    .ok();
+    let records: Vec<String> = records.iter().map(Json::to_string).collect();
+    println!("[\n{}\n]", records.join(",\n"));
+    if failed > 0 {
+        return Err(From::from(format!("{} of {} parts failed", failed, records.len())));
+    }
+    Ok(())
 }
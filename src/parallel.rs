@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Number of jobs to run at once when none is given: one per available core.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+/// Runs `work` on every item using up to `jobs` threads, and hands each result to `consume` in the
+/// same order as the items as soon as it and every result before it are ready.
+///
+/// If `consume` returns an error, no more items are started and that error is returned once the
+/// items already running have finished.
+pub fn run_ordered<I, T, E, W, C>(items: &[I], jobs: usize, work: W, mut consume: C) -> Result<(), E>
+where
+    I: Sync,
+    T: Send,
+    W: Fn(&I) -> T + Sync,
+    C: FnMut(T) -> Result<(), E>,
+{
+    let next_item = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        let (next_item, stopped, work) = (&next_item, &stopped, &work);
+        for _ in 0..jobs.max(1).min(items.len()) {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                while !stopped.load(Ordering::SeqCst) {
+                    let index = next_item.fetch_add(1, Ordering::SeqCst);
+                    match items.get(index) {
+                        Some(item) => {
+                            if sender.send((index, work(item))).is_err() {
+                                break;
+                            }
+                        }
+                        None => break,
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = HashMap::new();
+        let mut next_result = 0;
+        for (index, result) in receiver {
+            finished.insert(index, result);
+            while let Some(result) = finished.remove(&next_result) {
+                next_result += 1;
+                if let Err(err) = consume(result) {
+                    stopped.store(true, Ordering::SeqCst);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    use crate::input::Input;
+    use crate::registry::DAYS;
+
+    #[test]
+    fn consumes_results_in_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut results = Vec::new();
+        run_ordered(
+            &items,
+            4,
+            |&item| {
+                // Later items finish first, so results arrive out of order.
+                sleep(Duration::from_millis(20 - item));
+                item * 2
+            },
+            |result| {
+                results.push(result);
+                Ok::<(), ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(results, (0..20).map(|item| item * 2).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let items: Vec<u32> = (0..100).collect();
+        let started = AtomicUsize::new(0);
+        let mut consumed = Vec::new();
+        let result = run_ordered(
+            &items,
+            2,
+            |&item| {
+                started.fetch_add(1, Ordering::SeqCst);
+                sleep(Duration::from_millis(1));
+                item
+            },
+            |item| {
+                consumed.push(item);
+                if item == 3 {
+                    Err("stop")
+                } else {
+                    Ok(())
+                }
+            },
+        );
+        assert_eq!(result, Err("stop"));
+        assert_eq!(consumed, vec![0, 1, 2, 3]);
+        assert!(started.load(Ordering::SeqCst) < items.len());
+    }
+
+    #[test]
+    fn parses_days_with_shared_regexes_concurrently() {
+        let test_inputs = [
+            (3, "inputs/3_test.txt"),
+            (4, "inputs/4_test.txt"),
+            (6, "inputs/6_test.txt"),
+            (7, "inputs/7_test.txt"),
+            (9, "inputs/9_test.txt"),
+            (10, "inputs/10_test.txt"),
+            (12, "inputs/12_test.txt"),
+        ];
+        let solve = |&(day, input): &(u8, &str)| {
+            let day = DAYS.iter().find(|d| d.day == day).unwrap();
+            day.solve(&Input::file(input), 1).unwrap()
+        };
+        let serial: Vec<_> = test_inputs.iter().map(solve).collect();
+        let items: Vec<_> = test_inputs.iter().cycle().take(test_inputs.len() * 8).collect();
+        let mut concurrent = Vec::new();
+        run_ordered(&items, 8, |item| solve(item), |answer| {
+            concurrent.push(answer);
+            Ok::<(), ()>(())
+        })
+        .unwrap();
+        for (index, answer) in concurrent.iter().enumerate() {
+            assert_eq!(answer, &serial[index % test_inputs.len()]);
+        }
+    }
+}
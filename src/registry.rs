@@ -0,0 +1,451 @@
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9,
+};
+use crate::error::Result;
+use crate::input::Input;
+use crate::solution::{Answer, Solver};
+use crate::timing::Timing;
+
+pub struct Part {
+    pub part: u8,
+    pub returns: &'static str,
+}
+
+pub struct Day {
+    pub day: u8,
+    pub input: &'static str,
+    pub solution: &'static dyn Solver,
+    pub parts: [Part; 2],
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<&Part> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    pub fn solve(&self, input: &Input, part: u8) -> Result<Answer> {
+        self.solution.solve(input, part)
+    }
+
+    pub fn solve_timed(&self, input: &Input, part: u8) -> Result<(Answer, Timing)> {
+        self.solution.solve_timed(input, part)
+    }
+}
+
+pub static DAYS: [Day; 25] = [
+    Day {
+        day: 1,
+        input: day1::INPUT,
+        solution: &day1::Day1,
+        parts: [
+            Part {
+                part: 1,
+                returns: "resulting frequency after all changes",
+            },
+            Part {
+                part: 2,
+                returns: "first frequency reached twice",
+            },
+        ],
+    },
+    Day {
+        day: 2,
+        input: day2::INPUT,
+        solution: &day2::Day2,
+        parts: [
+            Part {
+                part: 1,
+                returns: "checksum of the box IDs",
+            },
+            Part {
+                part: 2,
+                returns: "common letters of the two correct box IDs",
+            },
+        ],
+    },
+    Day {
+        day: 3,
+        input: day3::INPUT,
+        solution: &day3::Day3,
+        parts: [
+            Part {
+                part: 1,
+                returns: "square inches within two or more claims",
+            },
+            Part {
+                part: 2,
+                returns: "ID of the only claim that doesn't overlap",
+            },
+        ],
+    },
+    Day {
+        day: 4,
+        input: day4::INPUT,
+        solution: &day4::Day4,
+        parts: [
+            Part {
+                part: 1,
+                returns: "sleepiest guard ID times their sleepiest minute",
+            },
+            Part {
+                part: 2,
+                returns: "guard ID times the minute most frequently slept",
+            },
+        ],
+    },
+    Day {
+        day: 5,
+        input: day5::INPUT,
+        solution: &day5::Day5,
+        parts: [
+            Part {
+                part: 1,
+                returns: "units remaining after fully reacting the polymer",
+            },
+            Part {
+                part: 2,
+                returns: "length of the shortest polymer with one unit type removed",
+            },
+        ],
+    },
+    Day {
+        day: 6,
+        input: day6::INPUT,
+        solution: &day6::Day6,
+        parts: [
+            Part {
+                part: 1,
+                returns: "size of the largest finite area",
+            },
+            Part {
+                part: 2,
+                returns: "size of the region near all coordinates",
+            },
+        ],
+    },
+    Day {
+        day: 7,
+        input: day7::INPUT,
+        solution: &day7::Day7,
+        parts: [
+            Part {
+                part: 1,
+                returns: "order the steps are completed in",
+            },
+            Part {
+                part: 2,
+                returns: "seconds for five workers to complete all steps",
+            },
+        ],
+    },
+    Day {
+        day: 8,
+        input: day8::INPUT,
+        solution: &day8::Day8,
+        parts: [
+            Part {
+                part: 1,
+                returns: "sum of all metadata entries",
+            },
+            Part {
+                part: 2,
+                returns: "value of the root node",
+            },
+        ],
+    },
+    Day {
+        day: 9,
+        input: day9::INPUT,
+        solution: &day9::Day9,
+        parts: [
+            Part {
+                part: 1,
+                returns: "winning elf's score",
+            },
+            Part {
+                part: 2,
+                returns: "winning elf's score with a last marble 100 times larger",
+            },
+        ],
+    },
+    Day {
+        day: 10,
+        input: day10::INPUT,
+        solution: &day10::Day10,
+        parts: [
+            Part {
+                part: 1,
+                returns: "message spelled out by the points in the sky",
+            },
+            Part {
+                part: 2,
+                returns: "seconds until the message appears",
+            },
+        ],
+    },
+    Day {
+        day: 11,
+        input: day11::INPUT,
+        solution: &day11::Day11,
+        parts: [
+            Part {
+                part: 1,
+                returns: "x,y of the 3x3 square with the largest total power",
+            },
+            Part {
+                part: 2,
+                returns: "x,y,size of the square with the largest total power",
+            },
+        ],
+    },
+    Day {
+        day: 12,
+        input: day12::INPUT,
+        solution: &day12::Day12,
+        parts: [
+            Part {
+                part: 1,
+                returns: "sum of the pots with plants after 20 generations",
+            },
+            Part {
+                part: 2,
+                returns: "sum of the pots with plants after fifty billion generations",
+            },
+        ],
+    },
+    Day {
+        day: 13,
+        input: day13::INPUT,
+        solution: &day13::Day13,
+        parts: [
+            Part {
+                part: 1,
+                returns: "x,y of the first crash",
+            },
+            Part {
+                part: 2,
+                returns: "x,y of the last cart left",
+            },
+        ],
+    },
+    Day {
+        day: 14,
+        input: day14::INPUT,
+        solution: &day14::Day14,
+        parts: [
+            Part {
+                part: 1,
+                returns: "scores of the ten recipes after the input count",
+            },
+            Part {
+                part: 2,
+                returns: "recipes to the left of the input score sequence",
+            },
+        ],
+    },
+    Day {
+        day: 15,
+        input: day15::INPUT,
+        solution: &day15::Day15,
+        parts: [
+            Part {
+                part: 1,
+                returns: "outcome of the combat",
+            },
+            Part {
+                part: 2,
+                returns: "outcome with the lowest elf attack power that loses no elves",
+            },
+        ],
+    },
+    Day {
+        day: 16,
+        input: day16::INPUT,
+        solution: &day16::Day16,
+        parts: [
+            Part {
+                part: 1,
+                returns: "samples behaving like three or more opcodes",
+            },
+            Part {
+                part: 2,
+                returns: "register 0 after running the test program",
+            },
+        ],
+    },
+    Day {
+        day: 17,
+        input: day17::INPUT,
+        solution: &day17::Day17,
+        parts: [
+            Part {
+                part: 1,
+                returns: "tiles the water can reach",
+            },
+            Part {
+                part: 2,
+                returns: "tiles of water left once the spring runs dry",
+            },
+        ],
+    },
+    Day {
+        day: 18,
+        input: day18::INPUT,
+        solution: &day18::Day18,
+        parts: [
+            Part {
+                part: 1,
+                returns: "resource value after 10 minutes",
+            },
+            Part {
+                part: 2,
+                returns: "resource value after a billion minutes",
+            },
+        ],
+    },
+    Day {
+        day: 19,
+        input: day19::INPUT,
+        solution: &day19::Day19,
+        parts: [
+            Part {
+                part: 1,
+                returns: "register 0 when the background process halts",
+            },
+            Part {
+                part: 2,
+                returns: "register 0 when the process halts after starting with register 0 at 1",
+            },
+        ],
+    },
+    Day {
+        day: 20,
+        input: day20::INPUT,
+        solution: &day20::Day20,
+        parts: [
+            Part {
+                part: 1,
+                returns: "doors on the shortest path to the furthest room",
+            },
+            Part {
+                part: 2,
+                returns: "rooms at least 1000 doors away",
+            },
+        ],
+    },
+    Day {
+        day: 21,
+        input: day21::INPUT,
+        solution: &day21::Day21,
+        parts: [
+            Part {
+                part: 1,
+                returns: "register 0 value that halts after the fewest instructions",
+            },
+            Part {
+                part: 2,
+                returns: "register 0 value that halts after the most instructions",
+            },
+        ],
+    },
+    Day {
+        day: 22,
+        input: day22::INPUT,
+        solution: &day22::Day22,
+        parts: [
+            Part {
+                part: 1,
+                returns: "total risk level of the rectangle up to the target",
+            },
+            Part {
+                part: 2,
+                returns: "fewest minutes to reach the target",
+            },
+        ],
+    },
+    Day {
+        day: 23,
+        input: day23::INPUT,
+        solution: &day23::Day23,
+        parts: [
+            Part {
+                part: 1,
+                returns: "nanobots in range of the strongest nanobot",
+            },
+            Part {
+                part: 2,
+                returns: "distance to the closest position in range of the most nanobots",
+            },
+        ],
+    },
+    Day {
+        day: 24,
+        input: day24::INPUT,
+        solution: &day24::Day24,
+        parts: [
+            Part {
+                part: 1,
+                returns: "units left in the winning army",
+            },
+            Part {
+                part: 2,
+                returns: "immune system units left with the smallest winning boost",
+            },
+        ],
+    },
+    Day {
+        day: 25,
+        input: day25::INPUT,
+        solution: &day25::Day25,
+        parts: [
+            Part {
+                part: 1,
+                returns: "number of constellations",
+            },
+            Part {
+                part: 2,
+                returns: "nothing, the last day has no second puzzle",
+            },
+        ],
+    },
+];
+
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_every_day_in_order() {
+        let days: Vec<u8> = DAYS.iter().map(|d| d.day).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn registers_both_parts_of_each_day() {
+        for day in DAYS.iter() {
+            assert!(day.part(1).is_some());
+            assert!(day.part(2).is_some());
+            assert!(day.part(3).is_none());
+        }
+    }
+
+    #[test]
+    fn solves_day_through_its_solution() {
+        let day = find_day(1).unwrap();
+        let input = Input::text("+1\n-2\n+3\n+1\n");
+        assert_eq!(day.solve(&input, 1).unwrap(), Answer::Integer(3));
+        assert_eq!(day.solve(&input, 2).unwrap(), Answer::Integer(2));
+        assert!(day.solve(&input, 3).is_err());
+    }
+
+    #[test]
+    fn finds_day() {
+        assert_eq!(find_day(11).unwrap().day, 11);
+        assert!(find_day(26).is_none());
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use std::time::Instant;
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::timing::Timing;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    Coordinate { x: usize, y: usize },
+    Subsection { x: usize, y: usize, size: usize },
+    Message { lines: Vec<String>, seconds: u32 },
+    None,
+}
+
+impl Answer {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Coordinate { .. } => "coordinate",
+            Answer::Subsection { .. } => "subsection",
+            Answer::Message { .. } => "message",
+            Answer::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(integer) => write!(f, "{}", integer),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Coordinate { x, y } => write!(f, "{},{}", x, y),
+            Answer::Subsection { x, y, size } => write!(f, "{},{},{}", x, y, size),
+            Answer::Message { lines, .. } => write!(f, "{}", lines.join("\n")),
+            Answer::None => write!(f, "none"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(integer: $integer) -> Answer {
+                    Answer::Integer(integer as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i32, i64, u32, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Answer {
+        match answer {
+            Some(answer) => answer.into(),
+            None => Answer::None,
+        }
+    }
+}
+
+/// A day's puzzle: parses the input once and then answers each part from the parsed value.
+pub trait Solution {
+    type Parsed;
+
+    fn parse(input: &Input) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<Answer>;
+    fn part2(parsed: &Self::Parsed) -> Result<Answer>;
+}
+
+/// Object-safe view of a `Solution` so that days with different parsed types can be stored
+/// together in the registry.
+pub trait Solver: Sync {
+    fn solve_timed(&self, input: &Input, part: u8) -> Result<(Answer, Timing)>;
+
+    fn solve(&self, input: &Input, part: u8) -> Result<Answer> {
+        Ok(self.solve_timed(input, part)?.0)
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn solve_timed(&self, input: &Input, part: u8) -> Result<(Answer, Timing)> {
+        let start = Instant::now();
+        let parsed = S::parse(input).map_err(|err| err.in_input(input))?;
+        let parsed_at = Instant::now();
+        let answer = match part {
+            1 => S::part1(&parsed)?,
+            2 => S::part2(&parsed)?,
+            _ => return Err(Error::simulation(format!("Invalid part: {}", part))),
+        };
+        let timing = Timing {
+            parse: parsed_at - start,
+            solve: parsed_at.elapsed(),
+        };
+        Ok((answer, timing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_answers() {
+        assert_eq!(Answer::from(-42).to_string(), "-42");
+        assert_eq!(Answer::from("CABDFE".to_string()).to_string(), "CABDFE");
+        assert_eq!(Answer::Coordinate { x: 7, y: 3 }.to_string(), "7,3");
+        assert_eq!(
+            Answer::Subsection {
+                x: 90,
+                y: 269,
+                size: 16
+            }
+            .to_string(),
+            "90,269,16"
+        );
+        assert_eq!(
+            Answer::Message {
+                lines: vec!["#..#".to_string(), "####".to_string()],
+                seconds: 3,
+            }
+            .to_string(),
+            "#..#\n####"
+        );
+    }
+
+    #[test]
+    fn converts_options_to_answers() {
+        assert_eq!(Answer::from(Some(14)), Answer::Integer(14));
+        assert_eq!(Answer::from(None::<String>), Answer::None);
+        assert_eq!(Answer::None.to_string(), "none");
+    }
+}
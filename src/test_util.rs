@@ -0,0 +1,22 @@
+/// A linear congruential generator, for scattering test data the same way on every run without
+/// depending on a random number crate.
+pub struct Lcg {
+    state: u32,
+}
+
+impl Lcg {
+    pub fn new(seed: u32) -> Lcg {
+        Lcg { state: seed }
+    }
+
+    /// The next number in `0..bound`.
+    pub fn below(&mut self, bound: u32) -> u32 {
+        self.state = self.state.wrapping_mul(1_103_515_245).wrapping_add(12345) % (1 << 31);
+        (self.state >> 16) % bound
+    }
+
+    /// The next number in `-span..=span`.
+    pub fn within(&mut self, span: i32) -> i32 {
+        self.below(2 * span as u32 + 1) as i32 - span
+    }
+}
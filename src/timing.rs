@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+/// How long a part took to parse its input, and to solve the puzzle from the parsed input.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// The fastest and median of several timings of the same run.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Summary {
+    pub fn of(samples: &[Duration]) -> Summary {
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let median = match samples.len() {
+            0 => Duration::default(),
+            len if len.is_multiple_of(2) => (samples[len / 2 - 1] + samples[len / 2]) / 2,
+            len => samples[len / 2],
+        };
+        Summary {
+            min: samples.first().cloned().unwrap_or_default(),
+            median,
+        }
+    }
+}
+
+/// Formats a duration with a unit that keeps it short, e.g. `850ns`, `12.5µs`, `3.2ms` or `1.45s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn summarizes_odd_number_of_samples() {
+        assert_eq!(
+            Summary::of(&millis(&[9, 3, 5])),
+            Summary {
+                min: Duration::from_millis(3),
+                median: Duration::from_millis(5),
+            }
+        );
+    }
+
+    #[test]
+    fn summarizes_even_number_of_samples() {
+        assert_eq!(
+            Summary::of(&millis(&[8, 2, 4, 100])),
+            Summary {
+                min: Duration::from_millis(2),
+                median: Duration::from_millis(6),
+            }
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(850)), "850ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_500)), "12.5µs");
+        assert_eq!(format_duration(Duration::from_micros(3_240)), "3.2ms");
+        assert_eq!(format_duration(Duration::from_millis(1_450)), "1.45s");
+    }
+}
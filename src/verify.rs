@@ -0,0 +1,213 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::{Error, ParseContext, Result};
+use crate::input::Input;
+use crate::registry::{self, DAYS};
+
+pub const ANSWERS: &str = "inputs/answers.txt";
+
+/// One line of the answers manifest: the answer a day's part should give for an input file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Expected {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Outcome {
+    Pass,
+    Mismatch { actual: String },
+    Fail(String),
+}
+
+impl FromStr for Expected {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Expected> {
+        let mut fields = s.splitn(4, ' ');
+        let mut next_field = |name| {
+            fields
+                .next()
+                .filter(|field| !field.is_empty())
+                .ok_or_else(|| Error::parse(1, s, format!("Malformed answer line, missing {}", name)))
+        };
+        let day = next_field("day")?.parse().on_line(1, s)?;
+        let part = next_field("part")?.parse().on_line(1, s)?;
+        let input = next_field("input")?.to_string();
+        let answer = next_field("answer")?.replace("\\n", "\n");
+        Ok(Expected {
+            day,
+            part,
+            input,
+            answer,
+        })
+    }
+}
+
+/// Reads the answers manifest, skipping blank lines and `#` comments.
+pub fn read_answers(input: &Input) -> Result<Vec<Expected>> {
+    let mut answers = Vec::new();
+    for (index, line) in input.reader()?.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        answers.push(line.parse().map_err(|err: Error| err.at_line(index + 1))?);
+    }
+    Ok(answers)
+}
+
+pub fn find_answer(answers: &[Expected], day: u8, part: u8) -> Option<&Expected> {
+    answers.iter().find(|expected| expected.day == day && expected.part == part)
+}
+
+/// Runs the registered solver for an expected answer against its input file.
+pub fn verify(expected: &Expected) -> Outcome {
+    let day = match registry::find_day(expected.day) {
+        Some(day) => day,
+        None => return Outcome::Fail(format!("Day {} has not been solved yet", expected.day)),
+    };
+    match day.solve(&Input::file(&expected.input), expected.part) {
+        Ok(answer) => {
+            let actual = answer.to_string();
+            if actual.trim_end() == expected.answer {
+                Outcome::Pass
+            } else {
+                Outcome::Mismatch { actual }
+            }
+        }
+        Err(Error::Io(err)) => Outcome::Fail(format!("{}: {}", expected.input, err)),
+        Err(err) => Outcome::Fail(err.to_string()),
+    }
+}
+
+/// Registered day and part pairs that have no expected answer in the manifest.
+pub fn unlisted(answers: &[Expected]) -> Vec<(u8, u8)> {
+    DAYS.iter()
+        .flat_map(|day| day.parts.iter().map(move |part| (day.day, part.part)))
+        .filter(|&(day, part)| find_answer(answers, day, part).is_none())
+        .collect()
+}
+
+/// Line-by-line diff of an expected and actual answer, marking expected lines with `-` and actual
+/// lines with `+`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => {
+                diff.push_str(&format!("  {}\n", expected))
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    diff.push_str(&format!("- {}\n", expected));
+                }
+                if let Some(actual) = actual {
+                    diff.push_str(&format!("+ {}\n", actual));
+                }
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_ANSWERS: &str = "\
+# day part input answer
+
+1 1 inputs/1_test.txt 3
+1 2 inputs/1_test_part2.txt 15
+10 1 inputs/10_test.txt #...#..###\\n#...#...#.\\n#...#...#.\\n#####...#.\\n#...#...#.\\n#...#...#.\\n#...#...#.\\n#...#..###
+";
+
+    #[test]
+    fn reads_answers() {
+        assert_eq!(
+            read_answers(&Input::text(TEST_ANSWERS)).unwrap(),
+            vec![
+                Expected {
+                    day: 1,
+                    part: 1,
+                    input: "inputs/1_test.txt".to_string(),
+                    answer: "3".to_string(),
+                },
+                Expected {
+                    day: 1,
+                    part: 2,
+                    input: "inputs/1_test_part2.txt".to_string(),
+                    answer: "15".to_string(),
+                },
+                Expected {
+                    day: 10,
+                    part: 1,
+                    input: "inputs/10_test.txt".to_string(),
+                    answer: concat!(
+                        "#...#..###\n#...#...#.\n#...#...#.\n#####...#.\n",
+                        "#...#...#.\n#...#...#.\n#...#...#.\n#...#..###",
+                    )
+                    .to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_on_malformed_answer_line() {
+        match read_answers(&Input::text("# comment\n1 1 inputs/1.txt\n")) {
+            Ok(_) => panic!("read_answers should have returned an error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "line 2: Malformed answer line, missing answer: \"1 1 inputs/1.txt\"",
+            ),
+        }
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let answers = read_answers(&Input::text(TEST_ANSWERS)).unwrap();
+        assert_eq!(verify(&answers[0]), Outcome::Pass);
+        assert_eq!(
+            verify(&answers[1]),
+            Outcome::Mismatch {
+                actual: "14".to_string()
+            }
+        );
+        assert_eq!(verify(&answers[2]), Outcome::Pass);
+    }
+
+    #[test]
+    fn fails_on_missing_input_file() {
+        let expected = Expected {
+            day: 1,
+            part: 1,
+            input: "inputs/missing.txt".to_string(),
+            answer: "3".to_string(),
+        };
+        match verify(&expected) {
+            Outcome::Fail(err) => assert!(err.starts_with("inputs/missing.txt: ")),
+            outcome => panic!("expected a failure, got {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn lists_registered_parts_without_answers() {
+        let answers = read_answers(&Input::text(TEST_ANSWERS)).unwrap();
+        let unlisted = unlisted(&answers);
+        assert!(!unlisted.contains(&(1, 1)));
+        assert!(unlisted.contains(&(10, 2)));
+    }
+
+    #[test]
+    fn diffs_answers_by_line() {
+        assert_eq!(diff("14", "15"), "- 14\n+ 15\n");
+        assert_eq!(diff("#.\n.#", "#.\n##\n.."), "  #.\n- .#\n+ ##\n+ ..\n");
+    }
+}
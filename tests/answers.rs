@@ -0,0 +1,21 @@
+extern crate advent_of_code_2018;
+
+use advent_of_code_2018::input::Input;
+use advent_of_code_2018::verify::{self, Outcome};
+
+fn verify_answer(day: u8, part: u8) {
+    let answers = verify::read_answers(&Input::file(verify::ANSWERS)).unwrap();
+    let expected = verify::find_answer(&answers, day, part).unwrap();
+    match verify::verify(expected) {
+        Outcome::Pass => {}
+        Outcome::Mismatch { actual } => panic!(
+            "Day {} part {} gave a different answer:\n{}",
+            day,
+            part,
+            verify::diff(&expected.answer, &actual)
+        ),
+        Outcome::Fail(err) => panic!("Day {} part {} failed: {}", day, part, err),
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/answers.rs"));
@@ -0,0 +1,18 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn names_stdin_in_parse_errors() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2018"))
+        .args(["run", "1", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"+1\nx\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("<stdin>:2:"), "{}", stderr);
+}